@@ -15,12 +15,21 @@
 //! * use the [`read`](fn.read.html) & [`poll`](fn.poll.html) functions on any, but same, thread
 //! * or the [`EventStream`](struct.EventStream.html).
 //!
+//! The same rules apply to every [`EventReader`](struct.EventReader.html) instance. An `EventReader`
+//! owns its own event source and can be used alongside the functions above.
+//!
 //! ## Mouse Events
 //!
 //! Mouse events are not enabled by default. You have to enable them with the
 //! [`EnableMouseCapture`](struct.EnableMouseCapture.html) command. See [Command API](../index.html#command-api)
 //! for more information.
 //!
+//! ## Paste Events
+//!
+//! Pasted text is reported as a sequence of key events by default. Enable bracketed paste
+//! with the [`EnableBracketedPaste`](struct.EnableBracketedPaste.html) command to receive
+//! the whole text in a single [`Event::Paste`](enum.Event.html#variant.Paste) event instead.
+//!
 //! ## Examples
 //!
 //! Blocking read:
@@ -34,7 +43,18 @@
 //!         match read()? {
 //!             Event::Key(event) => println!("{:?}", event),
 //!             Event::Mouse(event) => println!("{:?}", event),
+//!             Event::PixelMouse(event) => println!("{:?} in pixels", event),
+//!             Event::FocusGained => println!("FocusGained"),
+//!             Event::FocusLost => println!("FocusLost"),
+//!             Event::Paste(data) => println!("Pasted {:?}", data),
 //!             Event::Resize(width, height) => println!("New size {}x{}", width, height),
+//!             Event::Unknown(bytes) => println!("Unknown sequence {:?}", bytes),
+//!             Event::Suspend => println!("Suspend"),
+//!             Event::Resume => println!("Resume"),
+//!             Event::Signal(signal) => println!("Signal {}", signal),
+//!             Event::Hangup => break,
+//!             Event::Ready(token) => println!("Ready {}", token),
+//!             Event::Tick(id) => println!("Tick {}", id),
 //!         }
 //!     }
 //!     Ok(())
@@ -57,7 +77,18 @@
 //!             match read()? {
 //!                 Event::Key(event) => println!("{:?}", event),
 //!                 Event::Mouse(event) => println!("{:?}", event),
+//!                 Event::PixelMouse(event) => println!("{:?} in pixels", event),
+//!                 Event::FocusGained => println!("FocusGained"),
+//!                 Event::FocusLost => println!("FocusLost"),
+//!                 Event::Paste(data) => println!("Pasted {:?}", data),
 //!                 Event::Resize(width, height) => println!("New size {}x{}", width, height),
+//!                 Event::Unknown(bytes) => println!("Unknown sequence {:?}", bytes),
+//!                 Event::Suspend => println!("Suspend"),
+//!                 Event::Resume => println!("Resume"),
+//!                 Event::Signal(signal) => println!("Signal {}", signal),
+//!                 Event::Hangup => break,
+//!                 Event::Ready(token) => println!("Ready {}", token),
+//!                 Event::Tick(id) => println!("Tick {}", id),
 //!             }
 //!         } else {
 //!             // Timeout expired and no `Event` is available
@@ -70,34 +101,161 @@
 //! Check the [examples](https://github.com/crossterm-rs/crossterm/tree/master/examples) folder for more of
 //! them (`event-*`).
 
-use std::time::Duration;
+#[cfg(windows)]
+use std::io;
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+use std::{fmt, str::FromStr, time::Duration};
 
-use parking_lot::RwLock;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use bitflags::bitflags;
 use filter::{EventFilter, Filter};
+pub use gesture::{MouseGesture, MouseGestures};
+#[cfg(feature = "event-stream")]
+pub use gesture::{MouseGestureEvent, MouseGestureStream};
+pub use keymap::{Keymap, KeymapMatch};
+#[cfg(feature = "event-stream")]
+pub use keymap::{KeymapEvent, KeymapStream};
 use lazy_static::lazy_static;
+#[cfg(all(unix, feature = "async-io"))]
+pub use native_stream::AsyncIoEventStream;
+#[cfg(all(unix, feature = "tokio"))]
+pub use native_stream::TokioEventStream;
+pub use reader::EventReader;
+pub use recording::{EventRecorder, ReplayMode};
 #[cfg(feature = "event-stream")]
 pub use stream::EventStream;
-use timeout::PollTimeout;
 
-use crate::{Command, Result};
+use crate::{impl_display, Command, Result};
+#[cfg(unix)]
+use crate::{
+    style::Color,
+    terminal::{
+        ModeStatus, PrimaryDeviceAttributes, SecondaryDeviceAttributes, SuspendModes, TerminalMode,
+    },
+};
 
 mod ansi;
 pub(crate) mod filter;
+mod gesture;
+mod keymap;
+#[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+mod native_stream;
+#[cfg(unix)]
+pub(crate) mod query;
 mod read;
+mod reader;
+mod recording;
 mod source;
 #[cfg(feature = "event-stream")]
 mod stream;
 pub(crate) mod sys;
 mod timeout;
+mod timer;
 
 lazy_static! {
-    /// Static instance of `InternalEventReader`.
-    /// This needs to be static because there can be one event reader.
-    static ref INTERNAL_EVENT_READER: RwLock<read::InternalEventReader> = RwLock::new(read::InternalEventReader::default());
+    /// Static instance of `EventReader` used by the `poll` & `read` functions.
+    /// This needs to be static because there can be one event reader of the terminal.
+    static ref EVENT_READER: EventReader =
+        EventReader::from_internal_reader(read::InternalEventReader::default());
+}
+
+/// Registers a file descriptor with the global event reader to report its readiness
+/// for reading as [`Event::Ready`](enum.Event.html#variant.Ready) with the given `token`.
+///
+/// It lets an application wait for the terminal and its own sockets, pipes, ... in a single
+/// [`read`](fn.read.html) call. See
+/// [`EventReader::register_fd`](struct.EventReader.html#method.register_fd) for more details.
+///
+/// **This function is available on Unix only.**
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{io::Read, net::TcpStream};
+///
+/// use crossterm::{
+///     event::{read, register_fd, Event},
+///     Result,
+/// };
+///
+/// # #[cfg(unix)]
+/// fn run(mut stream: TcpStream) -> Result<()> {
+///     stream.set_nonblocking(true)?;
+///     register_fd(&stream, 1)?;
+///
+///     let mut buffer = [0; 1024];
+///     loop {
+///         match read()? {
+///             Event::Ready(1) => {
+///                 // Read everything available, the readiness is reported once
+///                 while let Ok(count) = stream.read(&mut buffer) {
+///                     if count == 0 {
+///                         return Ok(());
+///                     }
+///                     println!("Received {} bytes", count);
+///                 }
+///             }
+///             event => println!("{:?}", event),
+///         }
+///     }
+/// }
+/// ```
+#[cfg(unix)]
+pub fn register_fd<T>(source: &T, token: usize) -> Result<()>
+where
+    T: AsRawFd,
+{
+    EVENT_READER.register_fd(source, token)
+}
+
+/// Deregisters a file descriptor registered with the [`register_fd`](fn.register_fd.html)
+/// function.
+///
+/// **This function is available on Unix only.**
+#[cfg(unix)]
+pub fn deregister_fd<T>(source: &T) -> Result<()>
+where
+    T: AsRawFd,
+{
+    EVENT_READER.deregister_fd(source)
+}
+
+/// Enables the handling of the `SIGTSTP` signal by the global event reader, `None`
+/// disables it.
+///
+/// See [`EventReader::set_suspend_handling`](struct.EventReader.html#method.set_suspend_handling)
+/// for more details.
+///
+/// **This function is available on Unix only.**
+#[cfg(unix)]
+pub fn set_suspend_handling(modes: Option<SuspendModes>) -> Result<()> {
+    EVENT_READER.set_suspend_handling(modes)
+}
+
+/// Adds a one-shot timer to the global event reader, reported as
+/// [`Event::Tick`](enum.Event.html#variant.Tick) with the given `id` after the `delay`.
+///
+/// See [`EventReader::add_timer`](struct.EventReader.html#method.add_timer) for more details.
+pub fn add_timer(id: usize, delay: Duration) {
+    EVENT_READER.add_timer(id, delay);
+}
+
+/// Adds a periodic timer to the global event reader, reported as
+/// [`Event::Tick`](enum.Event.html#variant.Tick) with the given `id` every `interval`.
+///
+/// See [`EventReader::add_interval`](struct.EventReader.html#method.add_interval) for more
+/// details.
+pub fn add_interval(id: usize, interval: Duration) -> Result<()> {
+    EVENT_READER.add_interval(id, interval)
+}
+
+/// Removes a timer of the global event reader, returns `false` if there's no timer with
+/// the given `id`.
+pub fn remove_timer(id: usize) -> bool {
+    EVENT_READER.remove_timer(id)
 }
 
 /// Checks if there is an [`Event`](enum.Event.html) available.
@@ -191,22 +349,77 @@ pub fn read() -> Result<Event> {
     }
 }
 
+/// Sets how long to wait for the rest of an escape sequence before a lone `ESC` byte
+/// is reported as the `Esc` key.
+///
+/// It affects the [`poll`](fn.poll.html) and [`read`](fn.read.html) functions, see
+/// [`EventReader::set_escape_timeout`](struct.EventReader.html#method.set_escape_timeout)
+/// for more details.
+pub fn set_escape_timeout(timeout: Duration) {
+    EVENT_READER.set_escape_timeout(timeout);
+}
+
+/// Sets whether input sequences which can't be decoded are reported as
+/// [`Event::Unknown`](enum.Event.html#variant.Unknown) events by the [`poll`](fn.poll.html)
+/// and [`read`](fn.read.html) functions.
+///
+/// See [`EventReader::set_report_unknown_sequences`](struct.EventReader.html#method.set_report_unknown_sequences)
+/// for more details.
+pub fn set_report_unknown_sequences(report: bool) {
+    EVENT_READER.set_report_unknown_sequences(report);
+}
+
+/// Sets whether the SGR mouse reports read by the [`poll`](fn.poll.html) and
+/// [`read`](fn.read.html) functions are pixel positions, reported as
+/// [`Event::PixelMouse`](enum.Event.html#variant.PixelMouse).
+///
+/// See [`EventReader::set_pixel_mouse_reports`](struct.EventReader.html#method.set_pixel_mouse_reports)
+/// for more details.
+pub fn set_pixel_mouse_reports(pixels: bool) {
+    EVENT_READER.set_pixel_mouse_reports(pixels);
+}
+
+/// Starts recording all the events read by the [`poll`](fn.poll.html) and [`read`](fn.read.html)
+/// functions to the `writer`.
+///
+/// See [`EventReader::record`](struct.EventReader.html#method.record) for more details.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use crossterm::{event, Result};
+///
+/// fn main() -> Result<()> {
+///     event::record(File::create("events.txt")?)?;
+///
+///     loop {
+///         println!("{:?}", event::read()?);
+///     }
+/// }
+/// ```
+pub fn record<W>(writer: W) -> Result<()>
+where
+    W: std::io::Write + Send + Sync + 'static,
+{
+    EVENT_READER.record(writer)
+}
+
+/// Stops recording the events started with the [`record`](fn.record.html) function.
+///
+/// Returns the error which stopped the recording early, see
+/// [`EventReader::stop_recording`](struct.EventReader.html#method.stop_recording).
+pub fn stop_recording() -> Result<()> {
+    EVENT_READER.stop_recording()
+}
+
 /// Polls to check if there are any `InternalEvent`s that can be read within the given duration.
 pub(crate) fn poll_internal<F>(timeout: Option<Duration>, filter: &F) -> Result<bool>
 where
     F: Filter,
 {
-    let (mut reader, timeout) = if let Some(timeout) = timeout {
-        let poll_timeout = PollTimeout::new(Some(timeout));
-        if let Some(reader) = INTERNAL_EVENT_READER.try_write_for(timeout) {
-            (reader, poll_timeout.leftover())
-        } else {
-            return Ok(false);
-        }
-    } else {
-        (INTERNAL_EVENT_READER.write(), None)
-    };
-    reader.poll(timeout, filter)
+    EVENT_READER.poll_internal(timeout, filter)
 }
 
 /// Reads a single `InternalEvent`.
@@ -214,8 +427,7 @@ pub(crate) fn read_internal<F>(filter: &F) -> Result<InternalEvent>
 where
     F: Filter,
 {
-    let mut reader = INTERNAL_EVENT_READER.write();
-    reader.read(filter)
+    EVENT_READER.read_internal(filter)
 }
 
 /// A command that enables mouse event capturing.
@@ -233,7 +445,85 @@ impl Command for EnableMouseCapture {
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
-        sys::windows::enable_mouse_capture()
+        sys::windows::enable_mouse_capture(MouseTrackingMode::ButtonEvent)
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that enables mouse event capturing with the given
+/// [`MouseTrackingMode`](enum.MouseTrackingMode.html).
+///
+/// [`EnableMouseCapture`](struct.EnableMouseCapture.html) is equivalent to
+/// `EnableMouseTracking(MouseTrackingMode::ButtonEvent)`. Use
+/// [`DisableMouseCapture`](struct.DisableMouseCapture.html) to disable any of the modes.
+///
+/// Mouse events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableMouseTracking(pub MouseTrackingMode);
+
+impl Command for EnableMouseTracking {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        match self.0 {
+            MouseTrackingMode::Normal => ansi::ENABLE_NORMAL_MOUSE_MODE_CSI_SEQUENCE,
+            MouseTrackingMode::ButtonEvent => ansi::ENABLE_MOUSE_MODE_CSI_SEQUENCE,
+            MouseTrackingMode::AnyEvent => ansi::ENABLE_ANY_EVENT_MOUSE_MODE_CSI_SEQUENCE,
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::windows::enable_mouse_capture(self.0)
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that enables mouse event capturing with positions reported in pixels
+/// instead of cells (SGR-Pixels, mode 1016).
+///
+/// Terminals not supporting this mode fall back to reporting cell positions in the same
+/// format. Check the support with
+/// [`terminal::query_mode(TerminalMode::Dec(1016))`](../terminal/fn.query_mode.html) and
+/// enable [`set_pixel_mouse_reports`](fn.set_pixel_mouse_reports.html) if the mode is set.
+/// The mouse events are reported as [`Event::PixelMouse`](enum.Event.html#variant.PixelMouse)
+/// with the horizontal and vertical pixel positions then. Use
+/// [`terminal::window_size`](../terminal/fn.window_size.html) to get the cell size in pixels.
+/// Use [`DisableMouseCapture`](struct.DisableMouseCapture.html) to disable it.
+///
+/// Mouse events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
+///
+/// # Notes
+///
+/// * The pixel positions are set before the tracking starts and the tracking stops before
+///   they're reset by `DisableMouseCapture`, the terminal doesn't mix the cell & pixel
+///   positions.
+/// * The reader doesn't know which positions the terminal reports. Enable
+///   `set_pixel_mouse_reports` before executing this command and disable it after executing
+///   `DisableMouseCapture`, otherwise a mouse event read in between is reported with the
+///   wrong kind of positions.
+/// * This command is not supported on Windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnablePixelMouseCapture;
+
+impl Command for EnablePixelMouseCapture {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::ENABLE_PIXEL_MOUSE_MODE_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::other("Pixel mouse capture is not supported by the Windows API.").into())
     }
 
     #[cfg(windows)]
@@ -266,16 +556,280 @@ impl Command for DisableMouseCapture {
     }
 }
 
+/// A command that enables the [kitty keyboard protocol](https://sw.kovidgoyal.net/kitty/keyboard-protocol/),
+/// which adds extra information to keyboard events and removes ambiguity for modifier keys.
+///
+/// The flags are pushed onto a stack in the terminal, and have to be removed with the
+/// [`PopKeyboardEnhancementFlags`](struct.PopKeyboardEnhancementFlags.html) command.
+///
+/// # Notes
+///
+/// * Terminals not supporting the protocol silently ignore this command.
+/// * This command is not supported by the legacy Windows API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushKeyboardEnhancementFlags(pub KeyboardEnhancementFlags);
+
+impl Command for PushKeyboardEnhancementFlags {
+    type AnsiType = String;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::push_keyboard_enhancement_flags_csi_sequence(self.0.bits())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::other(
+            "Keyboard progressive enhancement is not supported by the legacy Windows API.",
+        )
+        .into())
+    }
+}
+
+/// A command that disables the extra keyboard reporting enabled by the last
+/// [`PushKeyboardEnhancementFlags`](struct.PushKeyboardEnhancementFlags.html) command.
+///
+/// # Notes
+///
+/// * This command is not supported by the legacy Windows API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PopKeyboardEnhancementFlags;
+
+impl Command for PopKeyboardEnhancementFlags {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::POP_KEYBOARD_ENHANCEMENT_FLAGS_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::other(
+            "Keyboard progressive enhancement is not supported by the legacy Windows API.",
+        )
+        .into())
+    }
+}
+
+/// A command that enables xterm's `modifyOtherKeys` mode (level 2).
+///
+/// Keys which are normally ambiguous, like `Ctrl+Shift+a`, `Ctrl+Enter` or `Ctrl+Tab`, are
+/// reported with all their modifiers. It's more widely supported than the kitty keyboard
+/// protocol (see [`PushKeyboardEnhancementFlags`](struct.PushKeyboardEnhancementFlags.html)).
+///
+/// It should be paired with [`DisableModifyOtherKeys`](struct.DisableModifyOtherKeys.html) at the end of execution.
+///
+/// # Notes
+///
+/// * Terminals not supporting this mode silently ignore this command.
+/// * This command is not supported by the legacy Windows API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableModifyOtherKeys;
+
+impl Command for EnableModifyOtherKeys {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::ENABLE_MODIFY_OTHER_KEYS_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::other(
+            "The modifyOtherKeys mode is not supported by the legacy Windows API.",
+        )
+        .into())
+    }
+}
+
+/// A command that disables xterm's `modifyOtherKeys` mode.
+///
+/// # Notes
+///
+/// * This command is not supported by the legacy Windows API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableModifyOtherKeys;
+
+impl Command for DisableModifyOtherKeys {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::DISABLE_MODIFY_OTHER_KEYS_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::other(
+            "The modifyOtherKeys mode is not supported by the legacy Windows API.",
+        )
+        .into())
+    }
+}
+
+/// A command that enables focus event emission.
+///
+/// It should be paired with [`DisableFocusChange`](struct.DisableFocusChange.html) at the end of execution.
+///
+/// Focus events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableFocusChange;
+
+impl Command for EnableFocusChange {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::ENABLE_FOCUS_CHANGE_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        // Focus events are always reported by the Windows console
+        Ok(())
+    }
+}
+
+/// A command that disables focus event emission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableFocusChange;
+
+impl Command for DisableFocusChange {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::DISABLE_FOCUS_CHANGE_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        // Focus events can't be disabled on Windows
+        Ok(())
+    }
+}
+
+/// A command that enables bracketed paste mode.
+///
+/// It should be paired with [`DisableBracketedPaste`](struct.DisableBracketedPaste.html) at the end of execution.
+///
+/// Pasted text is then reported as a single [`Event::Paste`](enum.Event.html#variant.Paste)
+/// instead of a sequence of key events.
+///
+/// # Notes
+///
+/// * This command is not supported by the legacy Windows API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableBracketedPaste;
+
+impl Command for EnableBracketedPaste {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::ENABLE_BRACKETED_PASTE_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::other("Bracketed paste is not supported by the legacy Windows API.").into())
+    }
+}
+
+/// A command that disables bracketed paste mode.
+///
+/// # Notes
+///
+/// * This command is not supported by the legacy Windows API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableBracketedPaste;
+
+impl Command for DisableBracketedPaste {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        ansi::DISABLE_BRACKETED_PASTE_CSI_SEQUENCE
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::other("Bracketed paste is not supported by the legacy Windows API.").into())
+    }
+}
+
+impl_display!(for PushKeyboardEnhancementFlags);
+impl_display!(for PopKeyboardEnhancementFlags);
+impl_display!(for EnableModifyOtherKeys);
+impl_display!(for DisableModifyOtherKeys);
+impl_display!(for EnableFocusChange);
+impl_display!(for DisableFocusChange);
+impl_display!(for EnableBracketedPaste);
+impl_display!(for DisableBracketedPaste);
+
 /// Represents an event.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Hash)]
 pub enum Event {
+    /// The terminal gained focus.
+    ///
+    /// Only emitted on UNIX if focus change reporting has been enabled with the
+    /// [`EnableFocusChange`](struct.EnableFocusChange.html) command.
+    FocusGained,
+    /// The terminal lost focus.
+    ///
+    /// Only emitted on UNIX if focus change reporting has been enabled with the
+    /// [`EnableFocusChange`](struct.EnableFocusChange.html) command.
+    FocusLost,
     /// A single key event with additional pressed modifiers.
     Key(KeyEvent),
     /// A single mouse event with additional pressed modifiers.
     Mouse(MouseEvent),
+    /// A single mouse event with the positions in pixels instead of cells.
+    ///
+    /// Only emitted on UNIX if the terminal supports the
+    /// [`EnablePixelMouseCapture`](struct.EnablePixelMouseCapture.html) mode and the reader
+    /// was told with the [`set_pixel_mouse_reports`](fn.set_pixel_mouse_reports.html)
+    /// function or the [`EventReader::set_pixel_mouse_reports`](struct.EventReader.html#method.set_pixel_mouse_reports)
+    /// method.
+    PixelMouse(MouseEvent),
+    /// A string that was pasted into the terminal. Only emitted if bracketed paste has been
+    /// enabled with the [`EnableBracketedPaste`](struct.EnableBracketedPaste.html) command.
+    Paste(String),
     /// An resize event with new dimensions after resize (columns, rows).
     Resize(u16, u16),
+    /// An input sequence which couldn't be decoded, the raw bytes.
+    ///
+    /// Only emitted on UNIX if enabled with the
+    /// [`set_report_unknown_sequences`](fn.set_report_unknown_sequences.html) function or
+    /// the [`EventReader::set_report_unknown_sequences`](struct.EventReader.html#method.set_report_unknown_sequences)
+    /// method.
+    Unknown(Vec<u8>),
+    /// The process was stopped by the `SIGTSTP` signal and continued.
+    ///
+    /// The terminal was restored right before the process was stopped, the event is reported
+    /// once it continues, followed by `Event::Resume`. Only emitted on UNIX by the readers
+    /// handling the suspension, see
+    /// [`EventReader::set_suspend_handling`](struct.EventReader.html#method.set_suspend_handling).
+    Suspend,
+    /// The process continued after it was stopped, the screen should be redrawn.
+    ///
+    /// The raw mode and the [`SuspendModes`](../terminal/struct.SuspendModes.html) are
+    /// reapplied at this point. Only emitted on UNIX by the readers handling the suspension.
+    Resume,
+    /// A signal registered with the
+    /// [`EventReader::with_signals`](struct.EventReader.html#method.with_signals) constructor
+    /// was received, the signal number.
+    ///
+    /// Only emitted on UNIX.
+    Signal(i32),
+    /// The input was closed (SSH disconnect, closed terminal window, end of the reader, ...).
+    ///
+    /// No more events are read from the input afterwards, the application should exit.
+    /// Only emitted on UNIX.
+    Hangup,
+    /// A file descriptor registered with the [`register_fd`](fn.register_fd.html) function
+    /// is ready for reading, the token given at the registration.
+    ///
+    /// Only emitted on UNIX.
+    Ready(usize),
+    /// A timer added with the [`add_timer`](fn.add_timer.html) or
+    /// [`add_interval`](fn.add_interval.html) function expired, the id of the timer.
+    Tick(usize),
 }
 
 /// Represents a mouse event.
@@ -308,6 +862,13 @@ pub enum MouseEvent {
     ///
     /// Contains the pressed mouse button, released pointer location (column, row), and additional key modifiers.
     Drag(MouseButton, u16, u16, KeyModifiers),
+    /// Moved mouse pointer while not pressing any mouse button.
+    ///
+    /// Contains the pointer location (column, row), and additional key modifiers.
+    ///
+    /// Only reported if mouse capture was enabled with
+    /// [`EnableMouseTracking(MouseTrackingMode::AnyEvent)`](struct.EnableMouseTracking.html).
+    Moved(u16, u16, KeyModifiers),
     /// Scrolled mouse wheel downwards (towards the user).
     ///
     /// Contains the scroll location (column, row), and additional key modifiers.
@@ -316,6 +877,25 @@ pub enum MouseEvent {
     ///
     /// Contains the scroll location (column, row), and additional key modifiers.
     ScrollUp(u16, u16, KeyModifiers),
+    /// Scrolled mouse wheel left (mostly on a laptop touchpad).
+    ///
+    /// Contains the scroll location (column, row), and additional key modifiers.
+    ScrollLeft(u16, u16, KeyModifiers),
+    /// Scrolled mouse wheel right (mostly on a laptop touchpad).
+    ///
+    /// Contains the scroll location (column, row), and additional key modifiers.
+    ScrollRight(u16, u16, KeyModifiers),
+}
+
+/// Represents which mouse events are reported by the terminal.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum MouseTrackingMode {
+    /// Report button presses, releases and the mouse wheel (mode 1000).
+    Normal,
+    /// Report motion while a button is pressed as well (mode 1002).
+    ButtonEvent,
+    /// Report all motion, including motion without a pressed button (mode 1003).
+    AnyEvent,
 }
 
 /// Represents a mouse button.
@@ -328,32 +908,137 @@ pub enum MouseButton {
     Right,
     /// Middle mouse button.
     Middle,
+    /// Back mouse button (X11 button 8).
+    Back,
+    /// Forward mouse button (X11 button 9).
+    Forward,
+    /// Any other mouse button.
+    ///
+    /// Contains the X11 button number (`10` or `11`).
+    Other(u8),
 }
 
 bitflags! {
-    /// Represents key modifiers (shift, control, alt).
-    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    /// Represents key modifiers (shift, control, alt, super, hyper, meta).
+    ///
+    /// `SUPER`, `HYPER` and `META` are reported only by terminals sending the full xterm
+    /// modifier parameter or implementing the kitty keyboard protocol.
     pub struct KeyModifiers: u8 {
         const SHIFT = 0b0000_0001;
         const CONTROL = 0b0000_0010;
         const ALT = 0b0000_0100;
+        const SUPER = 0b0000_1000;
+        const HYPER = 0b0001_0000;
+        const META = 0b0010_0000;
         const NONE = 0b0000_0000;
     }
 }
 
-/// Represents a key event.
+bitflags! {
+    /// Represents extra state about the key event (lock keys, keypad).
+    ///
+    /// The lock keys state is reported only by some terminals (kitty keyboard protocol, xterm
+    /// modifier parameter) and the Windows console, it's empty otherwise.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct KeyEventState: u8 {
+        /// Caps Lock was enabled for this key event.
+        const CAPS_LOCK = 0b0000_0001;
+        /// Num Lock was enabled for this key event.
+        const NUM_LOCK = 0b0000_0010;
+        /// The key event originates from the keypad.
+        const KEYPAD = 0b0000_0100;
+        const NONE = 0b0000_0000;
+    }
+}
+
+bitflags! {
+    /// Represents the kitty keyboard protocol enhancements to enable with the
+    /// [`PushKeyboardEnhancementFlags`](struct.PushKeyboardEnhancementFlags.html) command.
+    ///
+    /// See the [kitty documentation](https://sw.kovidgoyal.net/kitty/keyboard-protocol/#progressive-enhancement)
+    /// for the meaning of the individual flags.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct KeyboardEnhancementFlags: u8 {
+        /// Represent escape and modified keys using CSI-u sequences, so they can be unambiguously read.
+        const DISAMBIGUATE_ESCAPE_CODES = 0b0000_0001;
+        /// Add extra events with [`KeyEvent.kind`](struct.KeyEvent.html#structfield.kind) set to
+        /// [`KeyEventKind::Repeat`](enum.KeyEventKind.html#variant.Repeat) or
+        /// [`KeyEventKind::Release`](enum.KeyEventKind.html#variant.Release) when keys are
+        /// autorepeated or released.
+        const REPORT_EVENT_TYPES = 0b0000_0010;
+        /// Send the shifted version of a key alongside the key itself, allowing to report
+        /// e.g. `Shift + a` as an uppercase `A`.
+        const REPORT_ALTERNATE_KEYS = 0b0000_0100;
+        /// Represent all keyboard events as CSI-u sequences. This is required to get repeat/release
+        /// events for plain-text keys.
+        const REPORT_ALL_KEYS_AS_ESCAPE_CODES = 0b0000_1000;
+    }
+}
+
+/// Represents a keyboard event kind.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum KeyEventKind {
+    /// The key was pressed.
+    Press,
+    /// The key is being held down and was autorepeated.
+    Repeat,
+    /// The key was released.
+    Release,
+}
+
+/// Represents a key event.
+///
+/// # Platform-specific Notes
+///
+/// ## Key Event Kind
+///
+/// Only `KeyEventKind::Press` events are reported unless the terminal supports the kitty
+/// keyboard protocol and the `KeyboardEnhancementFlags::REPORT_EVENT_TYPES` flag was pushed
+/// with the [`PushKeyboardEnhancementFlags`](struct.PushKeyboardEnhancementFlags.html) command.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct KeyEvent {
     /// The key itself.
     pub code: KeyCode,
     /// Additional key modifiers.
     pub modifiers: KeyModifiers,
+    /// Kind of the event (press, repeat or release).
+    pub kind: KeyEventKind,
+    /// Lock keys and keypad state.
+    pub state: KeyEventState,
 }
 
 impl KeyEvent {
     pub fn new(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
-        KeyEvent { code, modifiers }
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        }
+    }
+
+    pub fn new_with_kind(code: KeyCode, modifiers: KeyModifiers, kind: KeyEventKind) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind,
+            state: KeyEventState::empty(),
+        }
+    }
+
+    pub fn new_with_kind_and_state(
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        kind: KeyEventKind,
+        state: KeyEventState,
+    ) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind,
+            state,
+        }
     }
 }
 
@@ -362,13 +1047,14 @@ impl From<KeyCode> for KeyEvent {
         KeyEvent {
             code,
             modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
         }
     }
 }
 
 /// Represents a key.
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum KeyCode {
     /// Backspace key.
     Backspace,
@@ -410,8 +1096,479 @@ pub enum KeyCode {
     Null,
     /// Escape key.
     Esc,
+    /// Caps Lock key.
+    CapsLock,
+    /// Scroll Lock key.
+    ScrollLock,
+    /// Num Lock key.
+    NumLock,
+    /// Print Screen key.
+    PrintScreen,
+    /// Pause key.
+    Pause,
+    /// Menu key.
+    Menu,
+    /// The "Begin" key (often mapped to the 5 key when Num Lock is turned off).
+    KeypadBegin,
+    /// A media key.
+    Media(MediaKeyCode),
+    /// A modifier key.
+    ///
+    /// These keys are reported only by terminals implementing the kitty keyboard protocol
+    /// with the `REPORT_ALL_KEYS_AS_ESCAPE_CODES` flag.
+    Modifier(ModifierKeyCode),
+}
+
+/// Represents a media key (as part of [`KeyCode::Media`](enum.KeyCode.html#variant.Media)).
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MediaKeyCode {
+    /// Play media key.
+    Play,
+    /// Pause media key.
+    Pause,
+    /// Play/Pause media key.
+    PlayPause,
+    /// Reverse media key.
+    Reverse,
+    /// Stop media key.
+    Stop,
+    /// Fast-forward media key.
+    FastForward,
+    /// Rewind media key.
+    Rewind,
+    /// Next-track media key.
+    TrackNext,
+    /// Previous-track media key.
+    TrackPrevious,
+    /// Record media key.
+    Record,
+    /// Lower-volume media key.
+    LowerVolume,
+    /// Raise-volume media key.
+    RaiseVolume,
+    /// Mute media key.
+    MuteVolume,
+}
+
+/// Represents a modifier key (as part of [`KeyCode::Modifier`](enum.KeyCode.html#variant.Modifier)).
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ModifierKeyCode {
+    /// Left Shift key.
+    LeftShift,
+    /// Left Control key.
+    LeftControl,
+    /// Left Alt key.
+    LeftAlt,
+    /// Left Super key.
+    LeftSuper,
+    /// Left Hyper key.
+    LeftHyper,
+    /// Left Meta key.
+    LeftMeta,
+    /// Right Shift key.
+    RightShift,
+    /// Right Control key.
+    RightControl,
+    /// Right Alt key.
+    RightAlt,
+    /// Right Super key.
+    RightSuper,
+    /// Right Hyper key.
+    RightHyper,
+    /// Right Meta key.
+    RightMeta,
+    /// Iso Level3 Shift key.
+    IsoLevel3Shift,
+    /// Iso Level5 Shift key.
+    IsoLevel5Shift,
 }
 
+// Names of the media & modifier keys used by the `KeyCode` `Display` & `FromStr` implementations
+const MEDIA_KEY_NAMES: [(MediaKeyCode, &str); 13] = [
+    (MediaKeyCode::Play, "mediaplay"),
+    (MediaKeyCode::Pause, "mediapause"),
+    (MediaKeyCode::PlayPause, "mediaplaypause"),
+    (MediaKeyCode::Reverse, "mediareverse"),
+    (MediaKeyCode::Stop, "mediastop"),
+    (MediaKeyCode::FastForward, "mediafastforward"),
+    (MediaKeyCode::Rewind, "mediarewind"),
+    (MediaKeyCode::TrackNext, "mediatracknext"),
+    (MediaKeyCode::TrackPrevious, "mediatrackprevious"),
+    (MediaKeyCode::Record, "mediarecord"),
+    (MediaKeyCode::LowerVolume, "volumedown"),
+    (MediaKeyCode::RaiseVolume, "volumeup"),
+    (MediaKeyCode::MuteVolume, "volumemute"),
+];
+
+const MODIFIER_KEY_NAMES: [(ModifierKeyCode, &str); 14] = [
+    (ModifierKeyCode::LeftShift, "leftshift"),
+    (ModifierKeyCode::LeftControl, "leftctrl"),
+    (ModifierKeyCode::LeftAlt, "leftalt"),
+    (ModifierKeyCode::LeftSuper, "leftsuper"),
+    (ModifierKeyCode::LeftHyper, "lefthyper"),
+    (ModifierKeyCode::LeftMeta, "leftmeta"),
+    (ModifierKeyCode::RightShift, "rightshift"),
+    (ModifierKeyCode::RightControl, "rightctrl"),
+    (ModifierKeyCode::RightAlt, "rightalt"),
+    (ModifierKeyCode::RightSuper, "rightsuper"),
+    (ModifierKeyCode::RightHyper, "righthyper"),
+    (ModifierKeyCode::RightMeta, "rightmeta"),
+    (ModifierKeyCode::IsoLevel3Shift, "isolevel3shift"),
+    (ModifierKeyCode::IsoLevel5Shift, "isolevel5shift"),
+];
+
+// Names of the key event state flags used by the `KeyEvent` `Display` & `FromStr`
+// implementations and the recordings
+pub(crate) const KEY_EVENT_STATE_NAMES: [(KeyEventState, &str); 3] = [
+    (KeyEventState::CAPS_LOCK, "capslock"),
+    (KeyEventState::NUM_LOCK, "numlock"),
+    (KeyEventState::KEYPAD, "keypad"),
+];
+
+/// An error returned when a key notation can't be parsed by the `FromStr` implementations
+/// of [`KeyEvent`](struct.KeyEvent.html), [`KeyCode`](enum.KeyCode.html) and
+/// [`KeyModifiers`](struct.KeyModifiers.html).
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Hash)]
+pub struct ParseKeyError {
+    kind: &'static str,
+    token: String,
+}
+
+impl ParseKeyError {
+    fn new(kind: &'static str, token: &str) -> ParseKeyError {
+        ParseKeyError {
+            kind,
+            token: token.to_string(),
+        }
+    }
+
+    /// Returns the part of the notation which couldn't be parsed, like `foo` in `ctrl-foo`.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} `{}`", self.kind, self.token)
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl fmt::Display for KeyModifiers {
+    /// Formats the modifiers as `ctrl-alt-shift-super-hyper-meta` (only the set ones), or `none`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("none");
+        }
+
+        let names = [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift"),
+            (KeyModifiers::SUPER, "super"),
+            (KeyModifiers::HYPER, "hyper"),
+            (KeyModifiers::META, "meta"),
+        ];
+
+        let mut first = true;
+        for (modifier, name) in names.iter() {
+            if self.contains(*modifier) {
+                if !first {
+                    f.write_str("-")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for KeyModifiers {
+    type Err = ParseKeyError;
+
+    /// Parses modifiers separated by `-` or `+`, like `ctrl-alt` or `C+S`.
+    ///
+    /// Modifier names are case insensitive. `none` or an empty string is parsed as no modifiers.
+    ///
+    /// The names are `shift` (`s`), `ctrl` (`control`, `c`), `alt` (`a`, `m`), `super` (`d`),
+    /// `hyper` and `meta`. Note that `m` is `ALT` like `M-` in the vim and emacs notation,
+    /// only `meta` is parsed as `META`.
+    fn from_str(src: &str) -> std::result::Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::empty();
+
+        if src.is_empty() || src.eq_ignore_ascii_case("none") {
+            return Ok(modifiers);
+        }
+
+        for name in src.split(&['-', '+'][..]) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "shift" | "s" => KeyModifiers::SHIFT,
+                "ctrl" | "control" | "c" => KeyModifiers::CONTROL,
+                // `M-` is Alt in the vim & emacs notation
+                "alt" | "a" | "m" => KeyModifiers::ALT,
+                "super" | "d" => KeyModifiers::SUPER,
+                "hyper" => KeyModifiers::HYPER,
+                "meta" => KeyModifiers::META,
+                _ => return Err(ParseKeyError::new("key modifier", name)),
+            };
+        }
+
+        Ok(modifiers)
+    }
+}
+
+impl fmt::Display for KeyCode {
+    /// Formats the key as `enter`, `pageup`, `f5`, `space`, `a`, `mediaplay`, `leftshift`, ...
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Null => f.write_str("null"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::CapsLock => f.write_str("capslock"),
+            KeyCode::ScrollLock => f.write_str("scrolllock"),
+            KeyCode::NumLock => f.write_str("numlock"),
+            KeyCode::PrintScreen => f.write_str("printscreen"),
+            KeyCode::Pause => f.write_str("pause"),
+            KeyCode::Menu => f.write_str("menu"),
+            KeyCode::KeypadBegin => f.write_str("keypadbegin"),
+            KeyCode::Media(media) => {
+                let (_, name) = MEDIA_KEY_NAMES
+                    .iter()
+                    .find(|(code, _)| code == media)
+                    .expect("missing media key name");
+                f.write_str(name)
+            }
+            KeyCode::Modifier(modifier) => {
+                let (_, name) = MODIFIER_KEY_NAMES
+                    .iter()
+                    .find(|(code, _)| code == modifier)
+                    .expect("missing modifier key name");
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+impl FromStr for KeyCode {
+    type Err = ParseKeyError;
+
+    /// Parses a key name like `enter`, `PageUp`, `F5`, `space` or a single character.
+    ///
+    /// Key names are case insensitive, single characters are not. Vim names like `CR`, `BS`
+    /// or `lt` are accepted as well.
+    fn from_str(src: &str) -> std::result::Result<Self, Self::Err> {
+        let mut chars = src.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(KeyCode::Char(c));
+        }
+
+        let name = src.to_ascii_lowercase();
+        let code = match name.as_str() {
+            "backspace" | "bs" => KeyCode::Backspace,
+            "enter" | "return" | "cr" => KeyCode::Enter,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "null" | "nul" => KeyCode::Null,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "minus" => KeyCode::Char('-'),
+            "plus" => KeyCode::Char('+'),
+            "lt" => KeyCode::Char('<'),
+            "gt" => KeyCode::Char('>'),
+            "capslock" => KeyCode::CapsLock,
+            "scrolllock" => KeyCode::ScrollLock,
+            "numlock" => KeyCode::NumLock,
+            "printscreen" | "print" => KeyCode::PrintScreen,
+            "pause" => KeyCode::Pause,
+            "menu" => KeyCode::Menu,
+            "keypadbegin" | "begin" => KeyCode::KeypadBegin,
+            _ if name.starts_with('f') => KeyCode::F(
+                name[1..]
+                    .parse()
+                    .map_err(|_| ParseKeyError::new("key", src))?,
+            ),
+            _ => {
+                if let Some((media, _)) = MEDIA_KEY_NAMES.iter().find(|(_, n)| *n == name) {
+                    KeyCode::Media(*media)
+                } else if let Some((modifier, _)) =
+                    MODIFIER_KEY_NAMES.iter().find(|(_, n)| *n == name)
+                {
+                    KeyCode::Modifier(*modifier)
+                } else {
+                    return Err(ParseKeyError::new("key", src));
+                }
+            }
+        };
+
+        Ok(code)
+    }
+}
+
+impl fmt::Display for KeyEvent {
+    /// Formats the event as `ctrl-alt-x`, `shift-f5`, `enter`, ...
+    ///
+    /// Repeat and release events get a `:repeat` or `:release` suffix, followed by the
+    /// `:keypad`, `:capslock` and `:numlock` suffixes of the state.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.modifiers.is_empty() {
+            write!(f, "{}-", self.modifiers)?;
+        }
+
+        write!(f, "{}", self.code)?;
+
+        match self.kind {
+            KeyEventKind::Press => {}
+            KeyEventKind::Repeat => f.write_str(":repeat")?,
+            KeyEventKind::Release => f.write_str(":release")?,
+        }
+
+        for (state, name) in KEY_EVENT_STATE_NAMES.iter() {
+            if self.state.contains(*state) {
+                write!(f, ":{}", name)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for KeyEvent {
+    type Err = ParseKeyError;
+
+    /// Parses a key event written as `ctrl-alt-x`, `shift+F5`, `C-x` or `<C-w>`.
+    ///
+    /// The modifiers are followed by the key, separated by `-` or `+`. Uppercase characters
+    /// and the `shift` modifier are normalized the same way the terminal input is, `A`,
+    /// `shift-a` and `shift-A` are all parsed as `KeyCode::Char('A')` with `KeyModifiers::SHIFT`.
+    ///
+    /// The key can be followed by the `:repeat`, `:release`, `:keypad`, `:capslock` and
+    /// `:numlock` suffixes.
+    fn from_str(src: &str) -> std::result::Result<Self, Self::Err> {
+        let mut src = src;
+        let mut kind = KeyEventKind::Press;
+        let mut state = KeyEventState::empty();
+
+        // `:` at the start is the key itself
+        while let Some(idx) = src.rfind(':').filter(|idx| *idx > 0) {
+            let suffix = &src[idx + 1..];
+            match suffix {
+                "repeat" => kind = KeyEventKind::Repeat,
+                "release" => kind = KeyEventKind::Release,
+                _ => match KEY_EVENT_STATE_NAMES
+                    .iter()
+                    .find(|(_, name)| *name == suffix)
+                {
+                    Some((flag, _)) => state |= *flag,
+                    None => break,
+                },
+            }
+            src = &src[..idx];
+        }
+
+        // Vim notation, `<C-w>`
+        let src = if src.len() > 2 && src.starts_with('<') && src.ends_with('>') {
+            &src[1..src.len() - 1]
+        } else {
+            src
+        };
+
+        // The last separator which isn't the key itself (`ctrl--`)
+        let (modifiers, code) = match src
+            .char_indices()
+            .rev()
+            .find(|(idx, c)| (*c == '-' || *c == '+') && idx + 1 < src.len())
+        {
+            Some((idx, _)) => (&src[..idx], &src[idx + 1..]),
+            None => ("", src),
+        };
+
+        let mut modifiers: KeyModifiers = modifiers.parse()?;
+        let mut code: KeyCode = code.parse()?;
+
+        match code {
+            KeyCode::Char(c) if c.is_uppercase() => modifiers |= KeyModifiers::SHIFT,
+            KeyCode::Char(c) if c.is_lowercase() && modifiers.contains(KeyModifiers::SHIFT) => {
+                let mut upper = c.to_uppercase();
+                if let (Some(upper), None) = (upper.next(), upper.next()) {
+                    code = KeyCode::Char(upper);
+                }
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => code = KeyCode::BackTab,
+            KeyCode::BackTab => modifiers |= KeyModifiers::SHIFT,
+            _ => {}
+        }
+
+        Ok(KeyEvent::new_with_kind_and_state(
+            code, modifiers, kind, state,
+        ))
+    }
+}
+
+/// Implements `Serialize` and `Deserialize` using the `Display` and `FromStr` string form.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_with_str {
+    ($type:ty) => {
+        impl Serialize for $type {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_serde_with_str!(KeyModifiers);
+#[cfg(feature = "serde")]
+impl_serde_with_str!(KeyCode);
+#[cfg(feature = "serde")]
+impl_serde_with_str!(KeyEvent);
+
 /// An internal event.
 ///
 /// Encapsulates publicly available `Event` with additional internal
@@ -423,4 +1580,181 @@ pub(crate) enum InternalEvent {
     /// A cursor position (`col`, `row`).
     #[cfg(unix)]
     CursorPosition(u16, u16),
+    /// The primary device attributes (DA1).
+    #[cfg(unix)]
+    PrimaryDeviceAttributes(PrimaryDeviceAttributes),
+    /// The secondary device attributes (DA2).
+    #[cfg(unix)]
+    SecondaryDeviceAttributes(SecondaryDeviceAttributes),
+    /// The terminal name and version (XTVERSION).
+    #[cfg(unix)]
+    TerminalVersion(String),
+    /// The status of a terminal mode (DECRQM).
+    #[cfg(unix)]
+    ModeReport(TerminalMode, ModeStatus),
+    /// A dynamic color of the terminal (`OSC` number, color).
+    #[cfg(unix)]
+    TerminalColor(u16, Color),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Command;
+
+    use super::{
+        DisableMouseCapture, EnablePixelMouseCapture, KeyCode, KeyEvent, KeyEventKind,
+        KeyEventState, KeyModifiers, MediaKeyCode, ModifierKeyCode,
+    };
+
+    #[test]
+    fn test_pixel_mouse_capture_modes_order() {
+        let position = |sequence: &str, mode| sequence.find(mode).unwrap();
+
+        let enable = EnablePixelMouseCapture.ansi_code();
+        assert!(position(enable, "?1016h") < position(enable, "?1000h"));
+        assert!(position(enable, "?1016h") < position(enable, "?1002h"));
+
+        // The cell positions are restored once the tracking is stopped
+        let disable = DisableMouseCapture.ansi_code();
+        assert!(position(disable, "?1000l") < position(disable, "?1016l"));
+        assert!(position(disable, "?1002l") < position(disable, "?1016l"));
+        assert!(position(disable, "?1003l") < position(disable, "?1016l"));
+    }
+
+    #[test]
+    fn test_parse_m_modifier_as_alt() {
+        assert_eq!("m".parse(), Ok(KeyModifiers::ALT));
+        assert_eq!("meta".parse(), Ok(KeyModifiers::META));
+    }
+
+    #[test]
+    fn test_parse_key_event_notations() {
+        let ctrl_alt_x = KeyEvent::new(
+            KeyCode::Char('x'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+        assert_eq!("ctrl-alt-x".parse(), Ok(ctrl_alt_x));
+        assert_eq!("Ctrl+Alt+x".parse(), Ok(ctrl_alt_x));
+        assert_eq!("C-M-x".parse(), Ok(ctrl_alt_x));
+        assert_eq!("<C-A-x>".parse(), Ok(ctrl_alt_x));
+
+        assert_eq!(
+            "shift+F5".parse(),
+            Ok(KeyEvent::new(KeyCode::F(5), KeyModifiers::SHIFT))
+        );
+        assert_eq!(
+            "<C-w>".parse(),
+            Ok(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL))
+        );
+        assert_eq!("<CR>".parse(), Ok(KeyEvent::from(KeyCode::Enter)));
+        assert_eq!("<lt>".parse(), Ok(KeyEvent::from(KeyCode::Char('<'))));
+        assert_eq!("space".parse(), Ok(KeyEvent::from(KeyCode::Char(' '))));
+        assert_eq!("PageDown".parse(), Ok(KeyEvent::from(KeyCode::PageDown)));
+        assert_eq!(
+            "ctrl--".parse(),
+            Ok(KeyEvent::new(KeyCode::Char('-'), KeyModifiers::CONTROL))
+        );
+        assert_eq!("+".parse(), Ok(KeyEvent::from(KeyCode::Char('+'))));
+    }
+
+    #[test]
+    fn test_parse_key_event_normalizes_shift() {
+        let shift_a = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert_eq!("A".parse(), Ok(shift_a));
+        assert_eq!("shift-a".parse(), Ok(shift_a));
+        assert_eq!("shift-A".parse(), Ok(shift_a));
+
+        let back_tab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!("shift-tab".parse(), Ok(back_tab));
+        assert_eq!("backtab".parse(), Ok(back_tab));
+    }
+
+    #[test]
+    fn test_parse_invalid_key_event() {
+        let token = |src: &str| src.parse::<KeyEvent>().unwrap_err().token().to_string();
+
+        assert_eq!(token(""), "");
+        assert_eq!(token("ctrl-"), "ctrl-");
+        assert_eq!(token("hyperdrive-x"), "hyperdrive");
+        assert_eq!(token("ctrl-foo"), "foo");
+        assert_eq!(token("f256"), "f256");
+        assert_eq!(
+            "ctrl-foo".parse::<KeyEvent>().unwrap_err().to_string(),
+            "invalid key `foo`"
+        );
+        assert_eq!(
+            "ctrl+hyperdrive"
+                .parse::<KeyModifiers>()
+                .unwrap_err()
+                .to_string(),
+            "invalid key modifier `hyperdrive`"
+        );
+    }
+
+    #[test]
+    fn test_key_event_display_round_trip() {
+        let events = [
+            KeyEvent::new(
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT,
+            ),
+            KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Char('-'), KeyModifiers::CONTROL),
+            KeyEvent::new(KeyCode::Char(':'), KeyModifiers::empty()),
+            KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()),
+            KeyEvent::new(KeyCode::F(12), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            KeyEvent::new(
+                KeyCode::Media(MediaKeyCode::PlayPause),
+                KeyModifiers::empty(),
+            ),
+            KeyEvent::new(
+                KeyCode::Modifier(ModifierKeyCode::RightAlt),
+                KeyModifiers::ALT,
+            ),
+            KeyEvent::new(KeyCode::PrintScreen, KeyModifiers::CONTROL),
+            KeyEvent::new_with_kind(KeyCode::Enter, KeyModifiers::CONTROL, KeyEventKind::Release),
+            KeyEvent::new_with_kind_and_state(
+                KeyCode::Enter,
+                KeyModifiers::empty(),
+                KeyEventKind::Press,
+                KeyEventState::KEYPAD,
+            ),
+            KeyEvent::new_with_kind_and_state(
+                KeyCode::Char(':'),
+                KeyModifiers::empty(),
+                KeyEventKind::Repeat,
+                KeyEventState::CAPS_LOCK | KeyEventState::NUM_LOCK,
+            ),
+        ];
+
+        for event in events.iter() {
+            assert_eq!(event.to_string().parse(), Ok(*event));
+        }
+
+        assert_eq!(events[0].to_string(), "ctrl-alt-x");
+        assert_eq!(events[1].to_string(), "shift-A");
+        assert_eq!(events[7].to_string(), "mediaplaypause");
+        assert_eq!(events[10].to_string(), "ctrl-enter:release");
+        assert_eq!(events[11].to_string(), "enter:keypad");
+        assert_eq!(events[12].to_string(), "::repeat:capslock:numlock");
+    }
+
+    #[test]
+    fn test_key_modifiers_display() {
+        assert_eq!(KeyModifiers::empty().to_string(), "none");
+        assert_eq!(
+            (KeyModifiers::SHIFT | KeyModifiers::CONTROL).to_string(),
+            "ctrl-shift"
+        );
+        assert_eq!("none".parse(), Ok(KeyModifiers::empty()));
+        assert_eq!(
+            (KeyModifiers::SUPER | KeyModifiers::META).to_string(),
+            "super-meta"
+        );
+        assert_eq!(
+            "hyper-meta-super".parse(),
+            Ok(KeyModifiers::SUPER | KeyModifiers::HYPER | KeyModifiers::META)
+        );
+    }
 }
@@ -9,9 +9,46 @@ pub(crate) const ENABLE_MOUSE_MODE_CSI_SEQUENCE: &str = concat!(
     csi!("?1006h")
 );
 
+pub(crate) const ENABLE_NORMAL_MOUSE_MODE_CSI_SEQUENCE: &str =
+    concat!(csi!("?1000h"), csi!("?1015h"), csi!("?1006h"));
+
+pub(crate) const ENABLE_ANY_EVENT_MOUSE_MODE_CSI_SEQUENCE: &str = concat!(
+    csi!("?1000h"),
+    csi!("?1002h"),
+    csi!("?1003h"),
+    csi!("?1015h"),
+    csi!("?1006h")
+);
+
+// SGR-Pixels is set before the tracking starts, no cell positions are reported in between
+pub(crate) const ENABLE_PIXEL_MOUSE_MODE_CSI_SEQUENCE: &str = concat!(
+    csi!("?1006h"),
+    csi!("?1016h"),
+    csi!("?1000h"),
+    csi!("?1002h")
+);
+
+// The tracking stops before the encodings are reset, no pixel positions are reported as cells
 pub(crate) const DISABLE_MOUSE_MODE_CSI_SEQUENCE: &str = concat!(
-    csi!("?1006l"),
-    csi!("?1015l"),
+    csi!("?1003l"),
     csi!("?1002l"),
-    csi!("?1000l")
+    csi!("?1000l"),
+    csi!("?1016l"),
+    csi!("?1006l"),
+    csi!("?1015l")
 );
+
+pub(crate) const ENABLE_FOCUS_CHANGE_CSI_SEQUENCE: &str = csi!("?1004h");
+pub(crate) const DISABLE_FOCUS_CHANGE_CSI_SEQUENCE: &str = csi!("?1004l");
+
+pub(crate) const ENABLE_BRACKETED_PASTE_CSI_SEQUENCE: &str = csi!("?2004h");
+pub(crate) const DISABLE_BRACKETED_PASTE_CSI_SEQUENCE: &str = csi!("?2004l");
+
+pub(crate) const ENABLE_MODIFY_OTHER_KEYS_CSI_SEQUENCE: &str = csi!(">4;2m");
+pub(crate) const DISABLE_MODIFY_OTHER_KEYS_CSI_SEQUENCE: &str = csi!(">4;0m");
+
+pub(crate) const POP_KEYBOARD_ENHANCEMENT_FLAGS_CSI_SEQUENCE: &str = csi!("<1u");
+
+pub(crate) fn push_keyboard_enhancement_flags_csi_sequence(flags: u8) -> String {
+    format!(csi!(">{}u"), flags)
+}
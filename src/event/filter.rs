@@ -1,4 +1,6 @@
 use crate::event::InternalEvent;
+#[cfg(unix)]
+use crate::terminal::TerminalMode;
 
 /// Interface for filtering an `InternalEvent`.
 pub(crate) trait Filter: Send + Sync + 'static {
@@ -17,6 +19,78 @@ impl Filter for CursorPositionFilter {
     }
 }
 
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct PrimaryDeviceAttributesFilter;
+
+#[cfg(unix)]
+impl Filter for PrimaryDeviceAttributesFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(*event, InternalEvent::PrimaryDeviceAttributes(_))
+    }
+}
+
+// The filters of the queries followed by the primary device attributes request
+// fulfill the primary device attributes as well.
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct SecondaryDeviceAttributesFilter;
+
+#[cfg(unix)]
+impl Filter for SecondaryDeviceAttributesFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(
+            *event,
+            InternalEvent::SecondaryDeviceAttributes(_) | InternalEvent::PrimaryDeviceAttributes(_)
+        )
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct TerminalVersionFilter;
+
+#[cfg(unix)]
+impl Filter for TerminalVersionFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(
+            *event,
+            InternalEvent::TerminalVersion(_) | InternalEvent::PrimaryDeviceAttributes(_)
+        )
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct ModeReportFilter(pub(crate) TerminalMode);
+
+#[cfg(unix)]
+impl Filter for ModeReportFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        match *event {
+            InternalEvent::ModeReport(mode, _) => mode == self.0,
+            InternalEvent::PrimaryDeviceAttributes(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct TerminalColorFilter(pub(crate) u16);
+
+#[cfg(unix)]
+impl Filter for TerminalColorFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        match *event {
+            InternalEvent::TerminalColor(code, _) => code == self.0,
+            InternalEvent::PrimaryDeviceAttributes(_) => true,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct EventFilter;
 
@@ -44,8 +118,11 @@ impl Filter for InternalEventFilter {
 #[cfg(test)]
 #[cfg(unix)]
 mod tests {
+    use crate::terminal::{ModeStatus, PrimaryDeviceAttributes, TerminalMode};
+
     use super::{
-        super::Event, CursorPositionFilter, EventFilter, Filter, InternalEvent, InternalEventFilter,
+        super::Event, CursorPositionFilter, EventFilter, Filter, InternalEvent,
+        InternalEventFilter, ModeReportFilter, TerminalVersionFilter,
     };
 
     #[test]
@@ -54,6 +131,36 @@ mod tests {
         assert!(CursorPositionFilter.eval(&InternalEvent::CursorPosition(0, 0)));
     }
 
+    #[test]
+    fn test_terminal_version_filter_filters_version_and_primary_device_attributes() {
+        let attributes = PrimaryDeviceAttributes {
+            class: 62,
+            features: vec![22],
+        };
+
+        assert!(TerminalVersionFilter.eval(&InternalEvent::TerminalVersion("xterm(367)".into())));
+        assert!(TerminalVersionFilter.eval(&InternalEvent::PrimaryDeviceAttributes(attributes)));
+        assert!(!TerminalVersionFilter.eval(&InternalEvent::CursorPosition(0, 0)));
+    }
+
+    #[test]
+    fn test_mode_report_filter_filters_requested_mode() {
+        let filter = ModeReportFilter(TerminalMode::Dec(2004));
+
+        assert!(filter.eval(&InternalEvent::ModeReport(
+            TerminalMode::Dec(2004),
+            ModeStatus::Set
+        )));
+        assert!(!filter.eval(&InternalEvent::ModeReport(
+            TerminalMode::Dec(1004),
+            ModeStatus::Set
+        )));
+        assert!(!filter.eval(&InternalEvent::ModeReport(
+            TerminalMode::Ansi(2004),
+            ModeStatus::Set
+        )));
+    }
+
     #[test]
     fn test_event_filter_filters_events() {
         assert!(EventFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
@@ -0,0 +1,350 @@
+//! Click counting and drag tracking of mouse events.
+
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "event-stream")]
+use std::pin::Pin;
+
+#[cfg(feature = "event-stream")]
+use futures_core::{
+    stream::Stream,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "event-stream")]
+use super::Event;
+use super::{MouseButton, MouseEvent};
+#[cfg(feature = "event-stream")]
+use crate::Result;
+
+/// The default maximum time between two clicks of a double click.
+const DEFAULT_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A result of feeding a [`MouseEvent`](enum.MouseEvent.html) to the
+/// [`MouseGestures`](struct.MouseGestures.html).
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum MouseGesture {
+    /// Pressed mouse button.
+    ///
+    /// Contains the `MouseEvent::Down` event and the click count (`1` for a single click,
+    /// `2` for a double click, `3` for a triple click, ...).
+    Down(MouseEvent, u32),
+    /// Released mouse button.
+    ///
+    /// Contains the `MouseEvent::Up` event and the click count of the press.
+    Up(MouseEvent, u32),
+    /// Moved mouse pointer for the first time since the button was pressed.
+    ///
+    /// Contains the `MouseEvent::Drag` event and the location (column, row) where the button
+    /// was pressed.
+    DragStart(MouseEvent, u16, u16),
+    /// Moved mouse pointer while dragging.
+    ///
+    /// Contains the `MouseEvent::Drag` event.
+    Drag(MouseEvent),
+    /// Released mouse button after dragging.
+    ///
+    /// Contains the `MouseEvent::Up` event and the location (column, row) where the drag
+    /// started.
+    DragEnd(MouseEvent, u16, u16),
+    /// Any other mouse event (moves without a pressed button, scrolling).
+    Other(MouseEvent),
+}
+
+/// Attaches click counts to mouse button presses and tracks dragging.
+///
+/// Feed mouse events with the [`feed`](#method.feed) method. Presses of the same button at
+/// the same location are counted as double, triple, ... clicks if they come within the click
+/// interval after each other. Dragging resets the click count.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{
+///     event::{read, Event, MouseGesture, MouseGestures},
+///     Result,
+/// };
+///
+/// fn run() -> Result<()> {
+///     let mut gestures = MouseGestures::new();
+///
+///     loop {
+///         if let Event::Mouse(event) = read()? {
+///             match gestures.feed(event) {
+///                 MouseGesture::Down(_, 2) => println!("Double click"),
+///                 MouseGesture::DragEnd(_, column, row) => {
+///                     println!("Dragged from {}x{}", column, row)
+///                 }
+///                 _ => {}
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MouseGestures {
+    click_interval: Duration,
+    // The button, location, time and click count of the last press
+    last_click: Option<(MouseButton, u16, u16, Instant, u32)>,
+    // The location and click count of the pressed button
+    pressed: Option<(u16, u16, u32)>,
+    dragging: bool,
+}
+
+impl Default for MouseGestures {
+    fn default() -> Self {
+        MouseGestures {
+            click_interval: DEFAULT_CLICK_INTERVAL,
+            last_click: None,
+            pressed: None,
+            dragging: false,
+        }
+    }
+}
+
+impl MouseGestures {
+    /// Constructs a new `MouseGestures` with the 500ms click interval.
+    pub fn new() -> MouseGestures {
+        MouseGestures::default()
+    }
+
+    /// Sets the maximum time between two presses counted as a double click.
+    pub fn set_click_interval(&mut self, interval: Duration) {
+        self.click_interval = interval;
+    }
+
+    /// Feeds the next mouse event.
+    pub fn feed(&mut self, event: MouseEvent) -> MouseGesture {
+        match event {
+            MouseEvent::Down(button, column, row, _) => {
+                let clicks = match self.last_click {
+                    Some((last_button, last_column, last_row, time, clicks))
+                        if last_button == button
+                            && last_column == column
+                            && last_row == row
+                            && time.elapsed() <= self.click_interval =>
+                    {
+                        clicks + 1
+                    }
+                    _ => 1,
+                };
+
+                self.last_click = Some((button, column, row, Instant::now(), clicks));
+                self.pressed = Some((column, row, clicks));
+                self.dragging = false;
+                MouseGesture::Down(event, clicks)
+            }
+            MouseEvent::Drag(_, column, row, _) => {
+                if self.dragging {
+                    return MouseGesture::Drag(event);
+                }
+
+                self.dragging = true;
+                self.last_click = None;
+                let (start_column, start_row, _) = self.pressed.unwrap_or((column, row, 1));
+                MouseGesture::DragStart(event, start_column, start_row)
+            }
+            MouseEvent::Up(_, column, row, _) => {
+                let pressed = self.pressed.take();
+
+                if self.dragging {
+                    self.dragging = false;
+                    let (start_column, start_row, _) = pressed.unwrap_or((column, row, 1));
+                    return MouseGesture::DragEnd(event, start_column, start_row);
+                }
+
+                MouseGesture::Up(event, pressed.map_or(1, |(_, _, clicks)| clicks))
+            }
+            _ => MouseGesture::Other(event),
+        }
+    }
+
+    /// Forgets the previous clicks and the pressed button.
+    pub fn reset(&mut self) {
+        self.last_click = None;
+        self.pressed = None;
+        self.dragging = false;
+    }
+
+    /// Returns a stream of the events from the given stream with the mouse events replaced
+    /// by the gestures.
+    ///
+    /// **This method is not available by default. You have to use the `event-stream` feature flag
+    /// to make it available.**
+    #[cfg(feature = "event-stream")]
+    pub fn into_stream<S>(self, stream: S) -> MouseGestureStream<S>
+    where
+        S: Stream<Item = Result<Event>> + Unpin,
+    {
+        MouseGestureStream {
+            stream,
+            gestures: self,
+        }
+    }
+}
+
+/// An item of the [`MouseGestureStream`](struct.MouseGestureStream.html).
+///
+/// **This type is not available by default. You have to use the `event-stream` feature flag
+/// to make it available.**
+#[cfg(feature = "event-stream")]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MouseGestureEvent {
+    /// A mouse event with the gesture information.
+    Gesture(MouseGesture),
+    /// Any other event than a mouse event.
+    Event(Event),
+}
+
+/// A stream adapter tracking the mouse events of an event stream with the
+/// [`MouseGestures`](struct.MouseGestures.html).
+///
+/// **This type is not available by default. You have to use the `event-stream` feature flag
+/// to make it available.**
+///
+/// Use the [`MouseGestures::into_stream`](struct.MouseGestures.html#method.into_stream) method
+/// to create it.
+#[cfg(feature = "event-stream")]
+#[derive(Debug)]
+pub struct MouseGestureStream<S> {
+    stream: S,
+    gestures: MouseGestures,
+}
+
+#[cfg(feature = "event-stream")]
+impl<S> MouseGestureStream<S> {
+    /// Returns the underlying gesture tracker.
+    pub fn gestures(&mut self) -> &mut MouseGestures {
+        &mut self.gestures
+    }
+}
+
+#[cfg(feature = "event-stream")]
+impl<S> Stream for MouseGestureStream<S>
+where
+    S: Stream<Item = Result<Event>> + Unpin,
+{
+    type Item = Result<MouseGestureEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        Pin::new(&mut this.stream).poll_next(cx).map(|item| {
+            item.map(|result| {
+                result.map(|event| match event {
+                    Event::Mouse(event) => MouseGestureEvent::Gesture(this.gestures.feed(event)),
+                    event => MouseGestureEvent::Event(event),
+                })
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::{
+        super::{KeyModifiers, MouseButton},
+        MouseEvent, MouseGesture, MouseGestures,
+    };
+
+    fn down(column: u16, row: u16) -> MouseEvent {
+        MouseEvent::Down(MouseButton::Left, column, row, KeyModifiers::NONE)
+    }
+
+    fn up(column: u16, row: u16) -> MouseEvent {
+        MouseEvent::Up(MouseButton::Left, column, row, KeyModifiers::NONE)
+    }
+
+    fn drag(column: u16, row: u16) -> MouseEvent {
+        MouseEvent::Drag(MouseButton::Left, column, row, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_click_count() {
+        let mut gestures = MouseGestures::new();
+
+        assert_eq!(gestures.feed(down(1, 1)), MouseGesture::Down(down(1, 1), 1));
+        assert_eq!(gestures.feed(up(1, 1)), MouseGesture::Up(up(1, 1), 1));
+        assert_eq!(gestures.feed(down(1, 1)), MouseGesture::Down(down(1, 1), 2));
+        assert_eq!(gestures.feed(up(1, 1)), MouseGesture::Up(up(1, 1), 2));
+        assert_eq!(gestures.feed(down(1, 1)), MouseGesture::Down(down(1, 1), 3));
+    }
+
+    #[test]
+    fn test_click_count_resets() {
+        let mut gestures = MouseGestures::new();
+        gestures.set_click_interval(Duration::from_millis(20));
+
+        gestures.feed(down(1, 1));
+        gestures.feed(up(1, 1));
+        // Another location
+        assert_eq!(gestures.feed(down(2, 1)), MouseGesture::Down(down(2, 1), 1));
+        gestures.feed(up(2, 1));
+        // Another button
+        let right = MouseEvent::Down(MouseButton::Right, 2, 1, KeyModifiers::NONE);
+        assert_eq!(gestures.feed(right), MouseGesture::Down(right, 1));
+
+        // Too late
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(gestures.feed(right), MouseGesture::Down(right, 1));
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut gestures = MouseGestures::new();
+
+        gestures.feed(down(1, 1));
+        assert_eq!(
+            gestures.feed(drag(2, 1)),
+            MouseGesture::DragStart(drag(2, 1), 1, 1)
+        );
+        assert_eq!(gestures.feed(drag(3, 2)), MouseGesture::Drag(drag(3, 2)));
+        assert_eq!(
+            gestures.feed(up(3, 2)),
+            MouseGesture::DragEnd(up(3, 2), 1, 1)
+        );
+
+        // Dragging isn't a click
+        assert_eq!(gestures.feed(down(1, 1)), MouseGesture::Down(down(1, 1), 1));
+    }
+
+    #[test]
+    fn test_other_events() {
+        let mut gestures = MouseGestures::new();
+        let scroll = MouseEvent::ScrollDown(1, 1, KeyModifiers::NONE);
+
+        assert_eq!(gestures.feed(scroll), MouseGesture::Other(scroll));
+    }
+
+    #[cfg(feature = "event-stream")]
+    #[test]
+    fn test_stream() {
+        use futures::{executor::block_on, stream, StreamExt};
+
+        use super::{super::Event, MouseGestureEvent};
+
+        let events = stream::iter(vec![
+            Ok(Event::Mouse(down(1, 1))),
+            Ok(Event::Resize(10, 10)),
+            Ok(Event::Mouse(down(1, 1))),
+        ]);
+
+        let results: Vec<_> = block_on(
+            MouseGestures::new()
+                .into_stream(events)
+                .map(|result| result.unwrap())
+                .collect(),
+        );
+
+        assert_eq!(
+            results,
+            vec![
+                MouseGestureEvent::Gesture(MouseGesture::Down(down(1, 1), 1)),
+                MouseGestureEvent::Event(Event::Resize(10, 10)),
+                MouseGestureEvent::Gesture(MouseGesture::Down(down(1, 1), 2)),
+            ]
+        );
+    }
+}
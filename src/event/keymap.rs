@@ -0,0 +1,691 @@
+//! Matching of key sequences to user defined actions.
+
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "event-stream")]
+use std::{collections::VecDeque, pin::Pin, sync::Arc, thread};
+
+#[cfg(feature = "event-stream")]
+use futures_core::{
+    stream::Stream,
+    task::{Context, Poll, Waker},
+};
+#[cfg(feature = "event-stream")]
+use parking_lot::{Condvar, Mutex, MutexGuard};
+
+#[cfg(feature = "event-stream")]
+use super::Event;
+use super::{KeyEvent, KeyEventKind, ParseKeyError};
+#[cfg(feature = "event-stream")]
+use crate::Result;
+
+/// A result of feeding [`KeyEvent`](struct.KeyEvent.html)s to a [`Keymap`](struct.Keymap.html).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum KeymapMatch<A> {
+    /// The keys completed a binding, contains its action.
+    Matched(A),
+    /// The keys don't match any binding, contains all the unmatched keys.
+    NoMatch(Vec<KeyEvent>),
+}
+
+/// Maps key sequences (like `g g` or `C-x C-s`) to user defined actions.
+///
+/// Feed key events with the [`feed`](#method.feed) method and it tells you if they
+/// matched a binding or didn't match at all. Nothing is returned while the keys are
+/// a prefix of a longer binding, see the [`pending`](#method.pending) method.
+///
+/// Only the key code and the modifiers are compared, release events are never matched.
+///
+/// # Prefixes
+///
+/// Once a key doesn't continue any binding, the longest bound prefix of the pending keys
+/// is matched and the keys after it are matched again from the start. If a binding is
+/// a prefix of another binding (like `g` and `g g`), `g` followed by `j` matches `g` and
+/// then `j`. Only the keys which don't start any binding are reported as `NoMatch`.
+///
+/// # Timeout
+///
+/// A pending sequence is resolved the same way if the next key comes later than the
+/// configured timeout. Call the [`expire`](#method.expire) method once the
+/// [`timeout_leftover`](#method.timeout_leftover) elapses to resolve it without waiting
+/// for the next key.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use crossterm::{
+///     event::{poll, read, Event, Keymap, KeymapMatch},
+///     Result,
+/// };
+///
+/// fn run() -> Result<()> {
+///     let mut keymap = Keymap::new();
+///     keymap.set_timeout(Some(Duration::from_millis(500)));
+///     keymap.bind_str("g g", "top").unwrap();
+///     keymap.bind_str("C-x C-s", "save").unwrap();
+///
+///     loop {
+///         let timeout = keymap.timeout_leftover().unwrap_or(Duration::from_secs(60));
+///
+///         let results = if poll(timeout)? {
+///             match read()? {
+///                 Event::Key(event) => keymap.feed(event),
+///                 _ => continue,
+///             }
+///         } else {
+///             keymap.expire()
+///         };
+///
+///         for result in results {
+///             match result {
+///                 KeymapMatch::Matched(action) => println!("{}", action),
+///                 KeymapMatch::NoMatch(keys) => println!("Unbound keys {:?}", keys),
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Keymap<A> {
+    bindings: Vec<(Vec<KeyEvent>, A)>,
+    pending: Vec<KeyEvent>,
+    timeout: Option<Duration>,
+    last_key: Option<Instant>,
+}
+
+impl<A> Default for Keymap<A> {
+    fn default() -> Self {
+        Keymap {
+            bindings: Vec::new(),
+            pending: Vec::new(),
+            timeout: None,
+            last_key: None,
+        }
+    }
+}
+
+impl<A: Clone> Keymap<A> {
+    /// Constructs a new, empty `Keymap` without a timeout.
+    pub fn new() -> Keymap<A> {
+        Keymap::default()
+    }
+
+    /// Sets the maximum time between two keys of a sequence.
+    ///
+    /// `None` (the default) waits for the next key indefinitely.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Binds the given key sequence to the action.
+    ///
+    /// An existing binding of the same sequence is replaced. Empty sequences are ignored.
+    pub fn bind<I>(&mut self, keys: I, action: A)
+    where
+        I: IntoIterator<Item = KeyEvent>,
+    {
+        let keys: Vec<KeyEvent> = keys.into_iter().collect();
+
+        if keys.is_empty() {
+            return;
+        }
+
+        match self
+            .bindings
+            .iter_mut()
+            .find(|(bound, _)| keys_match(bound, &keys))
+        {
+            Some(binding) => binding.1 = action,
+            None => self.bindings.push((keys, action)),
+        }
+    }
+
+    /// Binds the key sequence written in the key notation to the action.
+    ///
+    /// Keys are separated by whitespace, like `g g` or `C-x C-s`. See the `FromStr`
+    /// implementation of [`KeyEvent`](struct.KeyEvent.html) for the supported notation.
+    ///
+    /// Returns an error naming the key which can't be parsed, or the whole `keys` if there
+    /// isn't any key.
+    pub fn bind_str(&mut self, keys: &str, action: A) -> std::result::Result<(), ParseKeyError> {
+        let src = keys;
+        let keys = keys
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<std::result::Result<Vec<KeyEvent>, _>>()?;
+
+        if keys.is_empty() {
+            return Err(ParseKeyError::new("key sequence", src));
+        }
+
+        self.bind(keys, action);
+        Ok(())
+    }
+
+    /// Feeds the next key event.
+    ///
+    /// Returns the results of the keys resolved by this event in order, nothing if the
+    /// keys are a prefix of a longer binding. A sequence which timed out is resolved before
+    /// the event. Release events are reported as `NoMatch` and don't affect the pending
+    /// sequence.
+    pub fn feed(&mut self, event: KeyEvent) -> Vec<KeymapMatch<A>> {
+        if event.kind == KeyEventKind::Release {
+            return vec![KeymapMatch::NoMatch(vec![event])];
+        }
+
+        let mut results = self.expire();
+
+        self.pending.push(event);
+        self.resolve(false, &mut results);
+        results
+    }
+
+    /// Resolves the pending sequence if the timeout elapsed.
+    ///
+    /// Returns the results of all the pending keys, nothing if there's no pending sequence
+    /// or it didn't time out yet.
+    pub fn expire(&mut self) -> Vec<KeymapMatch<A>> {
+        let mut results = Vec::new();
+
+        if self.timeout_leftover() == Some(Duration::from_secs(0)) {
+            self.resolve(true, &mut results);
+        }
+
+        results
+    }
+
+    /// Returns the time left before the pending sequence times out.
+    ///
+    /// Returns `None` if there's no pending sequence or no timeout is set.
+    pub fn timeout_leftover(&self) -> Option<Duration> {
+        match (self.timeout, self.last_key) {
+            (Some(timeout), Some(last_key)) if !self.pending.is_empty() => {
+                Some(timeout.checked_sub(last_key.elapsed()).unwrap_or_default())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the keys of the pending sequence.
+    pub fn pending(&self) -> &[KeyEvent] {
+        &self.pending
+    }
+
+    /// Drops the pending sequence.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_key = None;
+    }
+
+    /// Returns a stream of the keymap results of the events from the given stream.
+    ///
+    /// **This method is not available by default. You have to use the `event-stream` feature flag
+    /// to make it available.**
+    #[cfg(feature = "event-stream")]
+    pub fn into_stream<S>(self, stream: S) -> KeymapStream<S, A>
+    where
+        S: Stream<Item = Result<Event>> + Unpin,
+    {
+        KeymapStream {
+            stream,
+            keymap: self,
+            events: VecDeque::new(),
+            timer: WakeTimer::default(),
+        }
+    }
+
+    // Matches the longest bound prefix of the pending keys and the keys after it, until
+    // the rest is a prefix of a longer binding (unless `all` keys have to be resolved)
+    fn resolve(&mut self, all: bool, results: &mut Vec<KeymapMatch<A>>) {
+        while !self.pending.is_empty() {
+            if !all && self.is_pending_prefix() {
+                self.last_key = Some(Instant::now());
+                return;
+            }
+
+            match self.longest_match() {
+                Some((len, action)) => {
+                    self.pending.drain(..len);
+                    results.push(KeymapMatch::Matched(action));
+                }
+                None => {
+                    // The first key doesn't start any binding followed by these keys
+                    let key = self.pending.remove(0);
+                    match results.last_mut() {
+                        Some(KeymapMatch::NoMatch(keys)) => keys.push(key),
+                        _ => results.push(KeymapMatch::NoMatch(vec![key])),
+                    }
+                }
+            }
+        }
+
+        self.last_key = None;
+    }
+
+    fn is_pending_prefix(&self) -> bool {
+        self.bindings.iter().any(|(keys, _)| {
+            keys.len() > self.pending.len()
+                && keys_match(&keys[..self.pending.len()], &self.pending)
+        })
+    }
+
+    // Returns the length & action of the longest binding the pending keys start with
+    fn longest_match(&self) -> Option<(usize, A)> {
+        self.bindings
+            .iter()
+            .filter(|(keys, _)| {
+                keys.len() <= self.pending.len() && keys_match(keys, &self.pending[..keys.len()])
+            })
+            .max_by_key(|(keys, _)| keys.len())
+            .map(|(keys, action)| (keys.len(), action.clone()))
+    }
+}
+
+fn keys_match(left: &[KeyEvent], right: &[KeyEvent]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .all(|(l, r)| l.code == r.code && l.modifiers == r.modifiers)
+}
+
+/// An item of the [`KeymapStream`](struct.KeymapStream.html).
+///
+/// **This type is not available by default. You have to use the `event-stream` feature flag
+/// to make it available.**
+#[cfg(feature = "event-stream")]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum KeymapEvent<A> {
+    /// Keys matched a binding, contains its action.
+    Action(A),
+    /// Keys didn't match any binding.
+    Keys(Vec<KeyEvent>),
+    /// Any other event than a key event.
+    Event(Event),
+}
+
+/// A stream adapter matching the key events of an event stream with a [`Keymap`](struct.Keymap.html).
+///
+/// **This type is not available by default. You have to use the `event-stream` feature flag
+/// to make it available.**
+///
+/// Pending sequences are not reported, timed out sequences are resolved without waiting
+/// for the next event. Use the [`Keymap::into_stream`](struct.Keymap.html#method.into_stream)
+/// method to create it.
+#[cfg(feature = "event-stream")]
+#[derive(Debug)]
+pub struct KeymapStream<S, A> {
+    stream: S,
+    keymap: Keymap<A>,
+    // Results of the resolved keys which weren't returned yet
+    events: VecDeque<KeymapEvent<A>>,
+    timer: WakeTimer,
+}
+
+#[cfg(feature = "event-stream")]
+impl<S, A> KeymapStream<S, A> {
+    /// Returns the underlying keymap.
+    pub fn keymap(&mut self) -> &mut Keymap<A> {
+        &mut self.keymap
+    }
+}
+
+#[cfg(feature = "event-stream")]
+fn keymap_event<A>(result: KeymapMatch<A>) -> KeymapEvent<A> {
+    match result {
+        KeymapMatch::Matched(action) => KeymapEvent::Action(action),
+        KeymapMatch::NoMatch(keys) => KeymapEvent::Keys(keys),
+    }
+}
+
+#[cfg(feature = "event-stream")]
+#[derive(Debug, Default)]
+struct WakeTimerState {
+    // When to wake the task up
+    deadline: Option<(Instant, Waker)>,
+    shutdown: bool,
+}
+
+/// Wakes the task of a stream up at the given deadline.
+///
+/// A single helper thread is started on the first deadline and reused for all the following
+/// ones, it exits once the timer is dropped.
+#[cfg(feature = "event-stream")]
+#[derive(Debug, Default)]
+struct WakeTimer {
+    shared: Option<Arc<(Mutex<WakeTimerState>, Condvar)>>,
+}
+
+#[cfg(feature = "event-stream")]
+impl WakeTimer {
+    /// Wakes the `waker` up at the `deadline`, replaces the previous deadline.
+    fn wake_at(&mut self, deadline: Instant, waker: Waker) {
+        let shared = self.shared.get_or_insert_with(|| {
+            let shared = Arc::new((Mutex::new(WakeTimerState::default()), Condvar::new()));
+            let thread_shared = shared.clone();
+            thread::spawn(move || wake_timer_thread(&thread_shared));
+            shared
+        });
+
+        let (state, condvar) = &**shared;
+        state.lock().deadline = Some((deadline, waker));
+        condvar.notify_one();
+    }
+}
+
+#[cfg(feature = "event-stream")]
+impl Drop for WakeTimer {
+    fn drop(&mut self) {
+        if let Some(shared) = &self.shared {
+            let (state, condvar) = &**shared;
+            state.lock().shutdown = true;
+            condvar.notify_one();
+        }
+    }
+}
+
+#[cfg(feature = "event-stream")]
+fn wake_timer_thread(shared: &(Mutex<WakeTimerState>, Condvar)) {
+    let (state, condvar) = shared;
+    let mut state = state.lock();
+
+    while !state.shutdown {
+        match state.deadline.as_ref().map(|(deadline, _)| *deadline) {
+            Some(deadline) if deadline <= Instant::now() => {
+                if let Some((_, waker)) = state.deadline.take() {
+                    MutexGuard::unlocked(&mut state, || waker.wake());
+                }
+            }
+            Some(deadline) => {
+                condvar.wait_for(
+                    &mut state,
+                    deadline.saturating_duration_since(Instant::now()),
+                );
+            }
+            None => condvar.wait(&mut state),
+        }
+    }
+}
+
+#[cfg(feature = "event-stream")]
+impl<S, A> Stream for KeymapStream<S, A>
+where
+    S: Stream<Item = Result<Event>> + Unpin,
+    A: Clone + Unpin,
+{
+    type Item = Result<KeymapEvent<A>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(event) = this.events.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(Event::Key(event)))) => {
+                    let results = this.keymap.feed(event);
+                    this.events.extend(results.into_iter().map(keymap_event));
+                }
+                Poll::Ready(Some(Ok(event))) => {
+                    // Resolve the timed out sequence first, the events are kept in order
+                    let results = this.keymap.expire();
+                    this.events.extend(results.into_iter().map(keymap_event));
+                    this.events.push_back(KeymapEvent::Event(event));
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => {
+                    let results = this.keymap.expire();
+                    if !results.is_empty() {
+                        this.events.extend(results.into_iter().map(keymap_event));
+                        continue;
+                    }
+
+                    // Wake the task up once the pending sequence times out
+                    if let Some(leftover) = this.keymap.timeout_leftover() {
+                        this.timer
+                            .wake_at(Instant::now() + leftover, cx.waker().clone());
+                    }
+
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    #[cfg(feature = "event-stream")]
+    use super::super::Event;
+    use super::{
+        super::{KeyCode, KeyModifiers},
+        KeyEvent, KeyEventKind, Keymap, KeymapMatch,
+    };
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::from(KeyCode::Char(c))
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_match_sequence() {
+        let mut keymap = Keymap::new();
+        keymap.bind_str("C-x C-s", "save").unwrap();
+        keymap.bind_str("C-x C-c", "quit").unwrap();
+
+        assert_eq!(keymap.feed(ctrl('x')), vec![]);
+        assert_eq!(keymap.pending(), &[ctrl('x')]);
+        assert_eq!(keymap.feed(ctrl('s')), vec![KeymapMatch::Matched("save")]);
+        assert!(keymap.pending().is_empty());
+
+        assert_eq!(keymap.feed(ctrl('x')), vec![]);
+        assert_eq!(keymap.feed(ctrl('c')), vec![KeymapMatch::Matched("quit")]);
+    }
+
+    #[test]
+    fn test_no_match_returns_unmatched_keys() {
+        let mut keymap = Keymap::new();
+        keymap.bind_str("g g", "top").unwrap();
+
+        assert_eq!(
+            keymap.feed(key('x')),
+            vec![KeymapMatch::NoMatch(vec![key('x')])]
+        );
+        assert_eq!(keymap.feed(key('g')), vec![]);
+        assert_eq!(
+            keymap.feed(key('x')),
+            vec![KeymapMatch::NoMatch(vec![key('g'), key('x')])]
+        );
+        assert!(keymap.pending().is_empty());
+    }
+
+    #[test]
+    fn test_prefix_binding_is_matched_on_divergence() {
+        let mut keymap = Keymap::new();
+        keymap.bind_str("g", "prefix").unwrap();
+        keymap.bind_str("g g", "top").unwrap();
+        keymap.bind_str("j", "down").unwrap();
+
+        // Without a timeout
+        assert_eq!(keymap.feed(key('g')), vec![]);
+        assert_eq!(
+            keymap.feed(key('j')),
+            vec![KeymapMatch::Matched("prefix"), KeymapMatch::Matched("down")]
+        );
+
+        assert_eq!(keymap.feed(key('g')), vec![]);
+        assert_eq!(keymap.feed(key('g')), vec![KeymapMatch::Matched("top")]);
+
+        assert_eq!(keymap.feed(key('g')), vec![]);
+        assert_eq!(
+            keymap.feed(key('x')),
+            vec![
+                KeymapMatch::Matched("prefix"),
+                KeymapMatch::NoMatch(vec![key('x')])
+            ]
+        );
+        assert!(keymap.pending().is_empty());
+    }
+
+    #[test]
+    fn test_breaking_key_starts_new_sequence() {
+        let mut keymap = Keymap::new();
+        keymap.bind_str("C-x C-s", "save").unwrap();
+        keymap.bind_str("g g", "top").unwrap();
+
+        assert_eq!(keymap.feed(ctrl('x')), vec![]);
+        assert_eq!(
+            keymap.feed(key('g')),
+            vec![KeymapMatch::NoMatch(vec![ctrl('x')])]
+        );
+        assert_eq!(keymap.pending(), &[key('g')]);
+        assert_eq!(keymap.feed(key('g')), vec![KeymapMatch::Matched("top")]);
+
+        // The longest bound prefix is matched, the keys after it are matched again
+        keymap.bind_str("C-x", "prefix").unwrap();
+        assert_eq!(keymap.feed(ctrl('x')), vec![]);
+        assert_eq!(keymap.feed(key('g')), vec![KeymapMatch::Matched("prefix")]);
+        assert_eq!(keymap.feed(key('g')), vec![KeymapMatch::Matched("top")]);
+    }
+
+    #[test]
+    fn test_release_events_are_ignored() {
+        let mut keymap = Keymap::new();
+        keymap.bind_str("g g", "top").unwrap();
+
+        let release = KeyEvent::new_with_kind(
+            KeyCode::Char('g'),
+            KeyModifiers::empty(),
+            KeyEventKind::Release,
+        );
+
+        assert_eq!(keymap.feed(key('g')), vec![]);
+        assert_eq!(
+            keymap.feed(release),
+            vec![KeymapMatch::NoMatch(vec![release])]
+        );
+        assert_eq!(keymap.feed(key('g')), vec![KeymapMatch::Matched("top")]);
+    }
+
+    #[test]
+    fn test_rebinding_replaces_action() {
+        let mut keymap = Keymap::new();
+        keymap.bind(vec![key('q')], 1);
+        keymap.bind(vec![key('q')], 2);
+
+        assert_eq!(keymap.feed(key('q')), vec![KeymapMatch::Matched(2)]);
+        assert_eq!(keymap.bind_str(" ", 3).unwrap_err().token(), " ");
+        assert_eq!(keymap.bind_str("g ctrl-foo", 3).unwrap_err().token(), "foo");
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut keymap = Keymap::new();
+        keymap.set_timeout(Some(Duration::from_millis(10)));
+        keymap.bind_str("g", "prefix").unwrap();
+        keymap.bind_str("g g", "top").unwrap();
+
+        assert_eq!(keymap.feed(key('g')), vec![]);
+        assert!(keymap.timeout_leftover().is_some());
+        assert_eq!(keymap.expire(), vec![]);
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(keymap.timeout_leftover(), Some(Duration::from_secs(0)));
+        assert_eq!(keymap.expire(), vec![KeymapMatch::Matched("prefix")]);
+        assert_eq!(keymap.timeout_leftover(), None);
+    }
+
+    #[test]
+    fn test_timed_out_sequence_is_resolved_by_next_key() {
+        let mut keymap = Keymap::new();
+        keymap.set_timeout(Some(Duration::from_millis(10)));
+        keymap.bind_str("g", "prefix").unwrap();
+        keymap.bind_str("g g", "top").unwrap();
+        keymap.bind_str("d d", "delete").unwrap();
+
+        assert_eq!(keymap.feed(key('g')), vec![]);
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(keymap.feed(key('g')), vec![KeymapMatch::Matched("prefix")]);
+        assert_eq!(keymap.feed(key('g')), vec![KeymapMatch::Matched("top")]);
+
+        assert_eq!(keymap.feed(key('d')), vec![]);
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(
+            keymap.feed(key('d')),
+            vec![KeymapMatch::NoMatch(vec![key('d')])]
+        );
+        assert_eq!(keymap.pending(), &[key('d')]);
+    }
+
+    #[cfg(feature = "event-stream")]
+    #[test]
+    fn test_stream() {
+        use futures::{executor::block_on, stream, StreamExt};
+
+        use super::KeymapEvent;
+
+        let mut keymap = Keymap::new();
+        keymap.bind_str("g", "prefix").unwrap();
+        keymap.bind_str("g g", "top").unwrap();
+
+        let events = stream::iter(vec![
+            Ok(Event::Key(key('g'))),
+            Ok(Event::Resize(10, 10)),
+            Ok(Event::Key(key('g'))),
+            Ok(Event::Key(key('g'))),
+            Ok(Event::Key(key('x'))),
+        ]);
+
+        let results: Vec<_> = block_on(
+            keymap
+                .into_stream(events)
+                .map(|result| result.unwrap())
+                .collect(),
+        );
+
+        assert_eq!(
+            results,
+            vec![
+                KeymapEvent::Event(Event::Resize(10, 10)),
+                KeymapEvent::Action("top"),
+                KeymapEvent::Action("prefix"),
+                KeymapEvent::Keys(vec![key('x')]),
+            ]
+        );
+    }
+
+    #[cfg(feature = "event-stream")]
+    #[test]
+    fn test_stream_resolves_timed_out_sequence() {
+        use futures::{executor::block_on, stream, StreamExt};
+
+        use super::KeymapEvent;
+
+        let mut keymap = Keymap::new();
+        keymap.set_timeout(Some(Duration::from_millis(10)));
+        keymap.bind_str("g", "prefix").unwrap();
+        keymap.bind_str("g g", "top").unwrap();
+
+        // The task is woken up without any new event
+        let events = stream::iter(vec![Ok(Event::Key(key('g')))]).chain(stream::pending());
+        let mut stream = keymap.into_stream(events);
+
+        for _ in 0..2 {
+            assert_eq!(
+                block_on(stream.next()).unwrap().unwrap(),
+                KeymapEvent::Action("prefix")
+            );
+            stream.keymap().feed(key('g'));
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Event streams polled by the reactors of the async runtimes.
+//!
+//! Unlike the [`EventStream`](struct.EventStream.html), these streams don't spawn a thread.
+//! The file descriptor of the event source (the `mio::Poll` of the terminal, signals, ...)
+//! is registered with the runtime reactor and the events are read without blocking once
+//! it's readable.
+
+#[cfg(feature = "async-io")]
+use std::os::unix::io::{AsFd, BorrowedFd};
+use std::{
+    future::Future,
+    os::unix::io::RawFd,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tokio")]
+use std::io;
+
+use futures_core::stream::Stream;
+
+use crate::Result;
+
+use super::{filter::EventFilter, Event, EventReader, InternalEvent};
+
+/// The file descriptor of the event source, it's owned by the source.
+#[derive(Debug)]
+struct SourceFd(RawFd);
+
+#[cfg(feature = "tokio")]
+impl mio_06::Evented for SourceFd {
+    fn register(
+        &self,
+        poll: &mio_06::Poll,
+        token: mio_06::Token,
+        interest: mio_06::Ready,
+        opts: mio_06::PollOpt,
+    ) -> io::Result<()> {
+        mio_06::unix::EventedFd(&self.0).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio_06::Poll,
+        token: mio_06::Token,
+        interest: mio_06::Ready,
+        opts: mio_06::PollOpt,
+    ) -> io::Result<()> {
+        mio_06::unix::EventedFd(&self.0).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio_06::Poll) -> io::Result<()> {
+        mio_06::unix::EventedFd(&self.0).deregister(poll)
+    }
+}
+
+#[cfg(feature = "async-io")]
+impl AsFd for SourceFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // The source keeps the file descriptor open as long as the stream holds the reader
+        unsafe { BorrowedFd::borrow_raw(self.0) }
+    }
+}
+
+/// Reads an event if it's available without waiting for it.
+///
+/// `None` is returned if there's no event or if the reader is used by another thread.
+fn next_event(reader: &EventReader) -> Option<Result<Event>> {
+    match reader.poll_internal(Some(Duration::from_secs(0)), &EventFilter) {
+        Ok(true) => match reader.read_internal(&EventFilter) {
+            Ok(InternalEvent::Event(event)) => Some(Ok(event)),
+            Err(e) => Some(Err(e)),
+            _ => unreachable!(),
+        },
+        Ok(false) => None,
+        Err(e) => Some(Err(e)),
+    }
+}
+
+/// A stream of `Result<Event>` polled by the [`tokio`](https://crates.io/crates/tokio) reactor.
+///
+/// **This type is not available by default. You have to use the `tokio` feature flag
+/// to make it available. It's available on Unix only.**
+///
+/// No thread is spawned, the event source is registered with the reactor of the runtime,
+/// which must have the I/O and time drivers enabled. Dropping the stream cancels any
+/// pending wait.
+///
+/// The stream reads events with the same reader as the [`poll`](fn.poll.html) &
+/// [`read`](fn.read.html) functions, use
+/// [`EventReader::tokio_stream`](struct.EventReader.html#method.tokio_stream) to get a stream
+/// of another reader. Don't read events from the reader with blocking calls at the same time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+///
+/// use crossterm::{event::TokioEventStream, Result};
+///
+/// async fn print_events() -> Result<()> {
+///     let mut stream = TokioEventStream::new()?;
+///
+///     while let Some(event) = stream.next().await {
+///         println!("{:?}", event?);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct TokioEventStream {
+    // Keep it first, it must be deregistered before the reader closes the file descriptor
+    source_fd: tokio::io::PollEvented<SourceFd>,
+    delay: Option<tokio::time::Delay>,
+    reader: EventReader,
+}
+
+#[cfg(feature = "tokio")]
+impl TokioEventStream {
+    /// Constructs a new `TokioEventStream` reading events with the global reader.
+    ///
+    /// It must be called within the tokio runtime.
+    pub fn new() -> Result<TokioEventStream> {
+        TokioEventStream::with_reader(super::EVENT_READER.clone())
+    }
+
+    /// Constructs a new `TokioEventStream` reading events with the given reader.
+    pub(crate) fn with_reader(reader: EventReader) -> Result<TokioEventStream> {
+        let source_fd = tokio::io::PollEvented::new_with_ready(
+            SourceFd(reader.raw_fd()?),
+            mio_06::Ready::readable(),
+        )?;
+
+        Ok(TokioEventStream {
+            source_fd,
+            delay: None,
+            reader,
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Stream for TokioEventStream {
+    type Item = Result<Event>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(result) = next_event(&this.reader) {
+                return Poll::Ready(Some(result));
+            }
+
+            // Wake up in time for the timers and the pending `Esc` key
+            if let Some(leftover) = this.reader.pending_leftover() {
+                let deadline = tokio::time::Instant::from_std(Instant::now() + leftover);
+
+                let delay = match this.delay.as_mut() {
+                    Some(delay) => {
+                        delay.reset(deadline);
+                        delay
+                    }
+                    None => this.delay.get_or_insert(tokio::time::delay_until(deadline)),
+                };
+
+                if Pin::new(delay).poll(cx).is_ready() {
+                    continue;
+                }
+            }
+
+            match this
+                .source_fd
+                .poll_read_ready(cx, mio_06::Ready::readable())
+            {
+                Poll::Ready(Ok(_)) => {
+                    // Everything was read, try once more after the readiness is cleared
+                    // not to miss an event which arrived in the meantime
+                    if let Err(e) = this
+                        .source_fd
+                        .clear_read_ready(cx, mio_06::Ready::readable())
+                    {
+                        return Poll::Ready(Some(Err(e.into())));
+                    }
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A stream of `Result<Event>` polled by the [`async-io`](https://crates.io/crates/async-io)
+/// reactor, used by [`async-std`](https://crates.io/crates/async-std) and
+/// [`smol`](https://crates.io/crates/smol).
+///
+/// **This type is not available by default. You have to use the `async-io` feature flag
+/// to make it available. It's available on Unix only.**
+///
+/// No thread is spawned, the event source is registered with the global `async-io` reactor.
+/// Dropping the stream cancels any pending wait.
+///
+/// The stream reads events with the same reader as the [`poll`](fn.poll.html) &
+/// [`read`](fn.read.html) functions, use
+/// [`EventReader::async_io_stream`](struct.EventReader.html#method.async_io_stream) to get
+/// a stream of another reader. Don't read events from the reader with blocking calls at
+/// the same time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+///
+/// use crossterm::{event::AsyncIoEventStream, Result};
+///
+/// async fn print_events() -> Result<()> {
+///     let mut stream = AsyncIoEventStream::new()?;
+///
+///     while let Some(event) = stream.next().await {
+///         println!("{:?}", event?);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "async-io")]
+#[derive(Debug)]
+pub struct AsyncIoEventStream {
+    // Keep it first, it must be deregistered before the reader closes the file descriptor
+    source_fd: async_io::Async<SourceFd>,
+    timer: async_io::Timer,
+    reader: EventReader,
+}
+
+#[cfg(feature = "async-io")]
+impl AsyncIoEventStream {
+    /// Constructs a new `AsyncIoEventStream` reading events with the global reader.
+    pub fn new() -> Result<AsyncIoEventStream> {
+        AsyncIoEventStream::with_reader(super::EVENT_READER.clone())
+    }
+
+    /// Constructs a new `AsyncIoEventStream` reading events with the given reader.
+    pub(crate) fn with_reader(reader: EventReader) -> Result<AsyncIoEventStream> {
+        Ok(AsyncIoEventStream {
+            source_fd: async_io::Async::new_nonblocking(SourceFd(reader.raw_fd()?))?,
+            timer: async_io::Timer::never(),
+            reader,
+        })
+    }
+}
+
+#[cfg(feature = "async-io")]
+impl Stream for AsyncIoEventStream {
+    type Item = Result<Event>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(result) = next_event(&this.reader) {
+                return Poll::Ready(Some(result));
+            }
+
+            // Wake up in time for the timers and the pending `Esc` key
+            if let Some(leftover) = this.reader.pending_leftover() {
+                this.timer.set_at(Instant::now() + leftover);
+
+                if Pin::new(&mut this.timer).poll(cx).is_ready() {
+                    continue;
+                }
+            }
+
+            match this.source_fd.poll_readable(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
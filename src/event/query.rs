@@ -0,0 +1,179 @@
+use std::{
+    io::{self, Write},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::{
+    terminal::{disable_raw_mode, enable_raw_mode, sys::is_raw_mode_enabled},
+    Result,
+};
+
+use super::{
+    filter::{Filter, PrimaryDeviceAttributesFilter},
+    poll_internal, read_internal,
+    timeout::PollTimeout,
+    InternalEvent,
+};
+
+/// The primary device attributes (DA1) request, answered by all terminals.
+const PRIMARY_DEVICE_ATTRIBUTES_REQUEST: &[u8] = b"\x1B[c";
+
+/// How long to wait for the response of the terminal.
+const QUERY_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// How long to poll the global reader before the responses read by other readers
+/// of the terminal are checked again.
+const FORWARDED_RESPONSE_INTERVAL: Duration = Duration::from_millis(10);
+
+lazy_static! {
+    /// The number of queries waiting for the response of the terminal.
+    pub(crate) static ref PENDING_QUERIES: AtomicUsize = AtomicUsize::new(0);
+    /// The responses read by the `EventReader`s of the terminal, they're handed to the
+    /// global reader which waits for them.
+    static ref FORWARDED_RESPONSES: Mutex<Vec<InternalEvent>> = Mutex::new(Vec::new());
+}
+
+/// Returns whether a query waits for the response of the terminal.
+pub(crate) fn is_query_pending() -> bool {
+    PENDING_QUERIES.load(Ordering::SeqCst) > 0
+}
+
+/// Hands the response read by an `EventReader` of the terminal to the queries.
+pub(crate) fn forward_response(response: InternalEvent) {
+    FORWARDED_RESPONSES.lock().push(response);
+}
+
+/// Writes the `request` to the terminal and reads the first response fulfilling the `filter`.
+///
+/// Events which don't fulfill the filter are kept in the queue. Raw mode is enabled for
+/// the time of the query if it's not enabled yet.
+pub(crate) fn query<F>(request: &[u8], filter: &F) -> Result<InternalEvent>
+where
+    F: Filter,
+{
+    with_pending_query(|| {
+        drain_stale_responses(filter)?;
+        write_request(request)?;
+        read_response(filter)?.ok_or_else(timeout_error)
+    })
+}
+
+/// Writes the `request` followed by the primary device attributes request to the terminal
+/// and reads the first response fulfilling the `filter`.
+///
+/// The `filter` has to fulfill the primary device attributes as well. Terminals answer the
+/// requests in order, `Ok(None)` is returned if the primary device attributes are the first
+/// response, the terminal doesn't support the `request`. It avoids waiting for a response
+/// which never comes.
+pub(crate) fn query_or_unsupported<F>(request: &[u8], filter: &F) -> Result<Option<InternalEvent>>
+where
+    F: Filter,
+{
+    with_pending_query(|| {
+        // A late DA1 of a previous query would be taken as the answer to this one
+        drain_stale_responses(filter)?;
+        write_request(&[request, PRIMARY_DEVICE_ATTRIBUTES_REQUEST].concat())?;
+
+        match read_response(filter)?.ok_or_else(timeout_error)? {
+            InternalEvent::PrimaryDeviceAttributes(_) => Ok(None),
+            response => {
+                // Don't leave the primary device attributes in the queue
+                read_response(&PrimaryDeviceAttributesFilter)?;
+                Ok(Some(response))
+            }
+        }
+    })
+}
+
+// Responses like OSC & DCS string sequences start with the same bytes as the `Alt` key
+// combinations (`ESC ]`, `ESC P`, ...), the global reader parses them only while a query
+// waits for the response
+fn with_pending_query<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    PENDING_QUERIES.fetch_add(1, Ordering::SeqCst);
+    let result = with_raw_mode(f);
+    PENDING_QUERIES.fetch_sub(1, Ordering::SeqCst);
+    result
+}
+
+fn with_raw_mode<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    if is_raw_mode_enabled() {
+        f()
+    } else {
+        enable_raw_mode()?;
+        let result = f();
+        disable_raw_mode()?;
+        result
+    }
+}
+
+fn write_request(request: &[u8]) -> Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(request)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Drops the responses fulfilling the `filter` which are already read or available, they
+/// answer an earlier query which timed out.
+fn drain_stale_responses<F>(filter: &F) -> Result<()>
+where
+    F: Filter,
+{
+    while take_forwarded_response(filter).is_some() {}
+
+    while poll_internal(Some(Duration::from_secs(0)), filter)? {
+        read_internal(filter)?;
+    }
+
+    Ok(())
+}
+
+/// Reads the first event fulfilling the `filter`, `None` if it isn't available in time.
+///
+/// The response may be read by the global reader or handed over by an `EventReader`
+/// of the terminal.
+fn read_response<F>(filter: &F) -> Result<Option<InternalEvent>>
+where
+    F: Filter,
+{
+    let timeout = PollTimeout::new(Some(QUERY_TIMEOUT));
+
+    while !timeout.elapsed() {
+        if let Some(response) = take_forwarded_response(filter) {
+            return Ok(Some(response));
+        }
+
+        let interval = timeout
+            .leftover()
+            .map_or(FORWARDED_RESPONSE_INTERVAL, |leftover| {
+                leftover.min(FORWARDED_RESPONSE_INTERVAL)
+            });
+
+        // An interrupted poll returns `Ok(false)` and is retried, other errors are returned
+        if poll_internal(Some(interval), filter)? {
+            return read_internal(filter).map(Some);
+        }
+    }
+
+    Ok(take_forwarded_response(filter))
+}
+
+/// Takes the first response fulfilling the `filter` handed over by other readers.
+pub(crate) fn take_forwarded_response<F>(filter: &F) -> Option<InternalEvent>
+where
+    F: Filter,
+{
+    let mut responses = FORWARDED_RESPONSES.lock();
+    let index = responses
+        .iter()
+        .position(|response| filter.eval(response))?;
+    Some(responses.remove(index))
+}
+
+fn timeout_error() -> crate::ErrorKind {
+    io::Error::other("The terminal response could not be read within a normal duration").into()
+}
@@ -1,5 +1,13 @@
-use std::{collections::vec_deque::VecDeque, io, time::Duration};
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+use std::{
+    collections::vec_deque::VecDeque,
+    io::{self, Write},
+    time::Duration,
+};
 
+#[cfg(unix)]
+use crate::terminal::SuspendModes;
 use crate::ErrorKind;
 
 #[cfg(unix)]
@@ -8,39 +16,151 @@ use super::source::unix::UnixInternalEventSource;
 use super::source::windows::WindowsEventSource;
 #[cfg(feature = "event-stream")]
 use super::sys::Waker;
-use super::{filter::Filter, source::EventSource, timeout::PollTimeout, InternalEvent, Result};
+use super::{
+    filter::Filter, recording::EventRecorder, source::EventSource, timeout::PollTimeout,
+    timer::Timers, Event, InternalEvent, Result,
+};
 
 /// Can be used to read `InternalEvent`s.
 pub(crate) struct InternalEventReader {
     events: VecDeque<InternalEvent>,
     source: Option<Box<dyn EventSource>>,
     skipped_events: Vec<InternalEvent>,
+    recorder: Option<EventRecorder<Box<dyn Write + Send + Sync>>>,
+    // The error which stopped the recording
+    recording_error: Option<ErrorKind>,
+    timers: Timers,
 }
 
 impl Default for InternalEventReader {
     fn default() -> Self {
-        #[cfg(windows)]
-        let source = WindowsEventSource::new();
-        #[cfg(unix)]
-        let source = UnixInternalEventSource::new();
+        InternalEventReader::new(default_source().ok())
+    }
+}
+
+/// Creates the platform event source reading from the terminal.
+pub(crate) fn default_source() -> Result<Box<dyn EventSource>> {
+    #[cfg(windows)]
+    let source = WindowsEventSource::new()?;
+    #[cfg(unix)]
+    let source = UnixInternalEventSource::new()?;
 
-        let source = source.ok().map(|x| Box::new(x) as Box<dyn EventSource>);
+    Ok(Box::new(source))
+}
 
+impl InternalEventReader {
+    /// Constructs a new `InternalEventReader` reading from the given source.
+    ///
+    /// `None` source makes `poll` and `read` fail, it's used when the platform
+    /// source can't be initialized.
+    pub(crate) fn new(source: Option<Box<dyn EventSource>>) -> Self {
         InternalEventReader {
             source,
             events: VecDeque::with_capacity(32),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
+        }
+    }
+
+    /// Sets whether the source reports unknown input sequences.
+    pub(crate) fn set_report_unknown_sequences(&mut self, report: bool) {
+        if let Some(source) = self.source.as_mut() {
+            source.set_report_unknown_sequences(report);
+        }
+    }
+
+    /// Sets whether the source reports the SGR mouse reports as `Event::PixelMouse`.
+    pub(crate) fn set_pixel_mouse_reports(&mut self, pixels: bool) {
+        if let Some(source) = self.source.as_mut() {
+            source.set_pixel_mouse_reports(pixels);
+        }
+    }
+
+    /// Records all events read from the source with the given recorder, `None` stops recording.
+    pub(crate) fn set_recorder(
+        &mut self,
+        recorder: Option<EventRecorder<Box<dyn Write + Send + Sync>>>,
+    ) {
+        self.recorder = recorder;
+        self.recording_error = None;
+    }
+
+    /// Returns the error which stopped the recording, if writing an event failed.
+    pub(crate) fn recording_result(&mut self) -> Result<()> {
+        match self.recording_error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Registers the file descriptor with the source to report its readiness as `Event::Ready`.
+    #[cfg(unix)]
+    pub(crate) fn register_fd(&mut self, fd: RawFd, token: usize) -> Result<()> {
+        self.source_mut()?.register_fd(fd, token)
+    }
+
+    /// Deregisters the file descriptor registered with the `register_fd` method.
+    #[cfg(unix)]
+    pub(crate) fn deregister_fd(&mut self, fd: RawFd) -> Result<()> {
+        self.source_mut()?.deregister_fd(fd)
+    }
+
+    /// Handles the `SIGTSTP` signal by the source, `None` stops handling it.
+    #[cfg(unix)]
+    pub(crate) fn set_suspend_handling(&mut self, modes: Option<SuspendModes>) -> Result<()> {
+        self.source_mut()?.set_suspend_handling(modes)
+    }
+
+    /// Adds a timer reported as `Event::Tick`, see `Timers::insert`.
+    pub(crate) fn add_timer(&mut self, id: usize, delay: Duration, interval: Option<Duration>) {
+        self.timers.insert(id, delay, interval);
+    }
+
+    /// Removes the timer, returns `false` if there's no timer with the given id.
+    pub(crate) fn remove_timer(&mut self, id: usize) -> bool {
+        self.timers.remove(id)
+    }
+
+    /// Returns a file descriptor which is readable when the source may have a new event.
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    pub(crate) fn raw_fd(&self) -> Result<RawFd> {
+        let source = self.source.as_ref().ok_or_else(source_not_initialized)?;
+
+        source.raw_fd().ok_or_else(|| {
+            io::Error::other("The event source can't be polled by an async runtime.").into()
+        })
+    }
+
+    /// Returns the time left before an event is available without any new input (an expired
+    /// timer, the pending `Esc` key, ...), `None` if it depends on the input only.
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    pub(crate) fn pending_leftover(&self) -> Option<Duration> {
+        let source = self
+            .source
+            .as_ref()
+            .and_then(|source| source.pending_leftover());
+
+        match (self.timers.leftover(), source) {
+            (Some(timer), Some(source)) => Some(timer.min(source)),
+            (timer, source) => timer.or(source),
         }
     }
-}
 
-impl InternalEventReader {
     /// Returns a `Waker` allowing to wake/force the `poll` method to return `Ok(false)`.
     #[cfg(feature = "event-stream")]
     pub(crate) fn waker(&self) -> Waker {
         self.source.as_ref().expect("reader source not set").waker()
     }
 
+    /// Sets the escape timeout of the event source.
+    pub(crate) fn set_escape_timeout(&mut self, timeout: Duration) {
+        if let Some(source) = self.source.as_mut() {
+            source.set_escape_timeout(timeout);
+        }
+    }
+
     pub(crate) fn poll<F>(&mut self, timeout: Option<Duration>, filter: &F) -> Result<bool>
     where
         F: Filter,
@@ -53,27 +173,46 @@ impl InternalEventReader {
 
         let event_source = match self.source.as_mut() {
             Some(source) => source,
-            None => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Failed to initialize input reader",
-                )
-                .into())
-            }
+            None => return Err(source_not_initialized()),
         };
 
         let poll_timeout = PollTimeout::new(timeout);
 
         loop {
-            let maybe_event = match event_source.try_read(poll_timeout.leftover()) {
-                Ok(None) => None,
+            // Don't wait longer than the nearest timer, the input is read without waiting
+            // before the ticks of expired timers
+            let source_timeout = match (poll_timeout.leftover(), self.timers.leftover()) {
+                (Some(leftover), Some(timer)) => Some(leftover.min(timer)),
+                (leftover, timer) => leftover.or(timer),
+            };
+
+            let mut due_tick = None;
+
+            let maybe_event = match event_source.try_read(source_timeout) {
+                Ok(None) => self
+                    .timers
+                    .expire()
+                    .map(|id| InternalEvent::Event(Event::Tick(id))),
                 Ok(Some(event)) => {
-                    if filter.eval(&event) {
-                        Some(event)
-                    } else {
-                        self.skipped_events.push(event);
-                        None
+                    // A due timer is reported right after the input, continuous input
+                    // (mouse motion, key repeat, a long paste, ...) doesn't starve it
+                    due_tick = self
+                        .timers
+                        .expire()
+                        .map(|id| InternalEvent::Event(Event::Tick(id)));
+
+                    if let (Some(recorder), InternalEvent::Event(event)) =
+                        (self.recorder.as_mut(), &event)
+                    {
+                        // The event is reported even if it can't be recorded, the recording
+                        // is stopped and the error is kept for `recording_result`
+                        if let Err(error) = recorder.record(event) {
+                            self.recorder = None;
+                            self.recording_error = Some(error);
+                        }
                     }
+
+                    Some(event)
                 }
                 Err(ErrorKind::IoError(e)) => {
                     if e.kind() == io::ErrorKind::Interrupted {
@@ -85,6 +224,19 @@ impl InternalEventReader {
                 Err(e) => return Err(e),
             };
 
+            let maybe_event = match maybe_event {
+                Some(event) if !filter.eval(&event) => {
+                    self.skipped_events.push(event);
+                    None
+                }
+                maybe_event => maybe_event,
+            };
+
+            // Queued after the read event, it's found by the next `poll` or `read` call
+            if let Some(tick) = due_tick {
+                self.skipped_events.push(tick);
+            }
+
             if poll_timeout.elapsed() || maybe_event.is_some() {
                 self.events.extend(self.skipped_events.drain(..));
 
@@ -98,6 +250,11 @@ impl InternalEventReader {
         }
     }
 
+    #[cfg(unix)]
+    fn source_mut(&mut self) -> Result<&mut Box<dyn EventSource>> {
+        self.source.as_mut().ok_or_else(source_not_initialized)
+    }
+
     pub(crate) fn read<F>(&mut self, filter: &F) -> Result<InternalEvent>
     where
         F: Filter,
@@ -129,9 +286,13 @@ impl InternalEventReader {
     }
 }
 
+fn source_not_initialized() -> ErrorKind {
+    io::Error::other("Failed to initialize input reader").into()
+}
+
 #[cfg(test)]
-mod tests {
-    use std::{collections::VecDeque, time::Duration};
+pub(super) mod tests {
+    use std::{collections::VecDeque, thread, time::Duration};
 
     use crate::ErrorKind;
 
@@ -139,7 +300,7 @@ mod tests {
     use super::super::filter::CursorPositionFilter;
     use super::{
         super::{filter::InternalEventFilter, Event},
-        EventSource, InternalEvent, InternalEventReader,
+        EventSource, InternalEvent, InternalEventReader, Timers,
     };
 
     #[test]
@@ -148,6 +309,9 @@ mod tests {
             events: VecDeque::new(),
             source: None,
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert!(reader.poll(None, &InternalEventFilter).is_err());
@@ -165,6 +329,9 @@ mod tests {
             events: vec![InternalEvent::Event(Event::Resize(10, 10))].into(),
             source: None,
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert!(reader.poll(None, &InternalEventFilter).unwrap());
@@ -181,6 +348,9 @@ mod tests {
             .into(),
             source: None,
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert!(reader.poll(None, &CursorPositionFilter).unwrap());
@@ -194,6 +364,9 @@ mod tests {
             events: vec![EVENT].into(),
             source: None,
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
@@ -208,6 +381,9 @@ mod tests {
             events: vec![InternalEvent::Event(Event::Resize(10, 10)), CURSOR_EVENT].into(),
             source: None,
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&CursorPositionFilter).unwrap(), CURSOR_EVENT);
@@ -223,6 +399,9 @@ mod tests {
             events: vec![SKIPPED_EVENT, CURSOR_EVENT].into(),
             source: None,
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&CursorPositionFilter).unwrap(), CURSOR_EVENT);
@@ -237,6 +416,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert!(!reader
@@ -252,6 +434,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert!(reader.poll(None, &InternalEventFilter).unwrap());
@@ -270,6 +455,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
@@ -285,6 +473,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
@@ -302,6 +493,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
@@ -320,6 +514,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(
@@ -342,6 +539,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(
@@ -369,6 +569,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
@@ -391,6 +594,9 @@ mod tests {
             events: VecDeque::new(),
             source: Some(Box::new(source)),
             skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers: Timers::default(),
         };
 
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
@@ -398,8 +604,68 @@ mod tests {
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
     }
 
+    #[test]
+    fn test_input_is_read_before_expired_timers() {
+        const EVENT: InternalEvent = InternalEvent::Event(Event::Resize(10, 10));
+
+        let mut timers = Timers::default();
+        timers.insert(1, Duration::from_secs(0), Some(Duration::from_secs(60)));
+
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: Some(Box::new(FakeSource::with_events(&[EVENT, EVENT]))),
+            skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers,
+        };
+
+        assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
+        assert_eq!(
+            reader.read(&InternalEventFilter).unwrap(),
+            InternalEvent::Event(Event::Tick(1))
+        );
+        assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
+    }
+
+    #[test]
+    fn test_continuous_input_does_not_starve_timers() {
+        let mut timers = Timers::default();
+        timers.insert(1, Duration::from_millis(20), None);
+
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: Some(Box::new(EndlessSource)),
+            skipped_events: Vec::with_capacity(32),
+            recorder: None,
+            recording_error: None,
+            timers,
+        };
+
+        let tick = InternalEvent::Event(Event::Tick(1));
+        assert!((0..1_000).any(|_| reader.read(&InternalEventFilter).unwrap() == tick));
+    }
+
+    // Has a new event whenever it's read, like a terminal flooded with mouse motion
+    struct EndlessSource;
+
+    impl EventSource for EndlessSource {
+        fn try_read(
+            &mut self,
+            _timeout: Option<Duration>,
+        ) -> Result<Option<InternalEvent>, ErrorKind> {
+            thread::sleep(Duration::from_millis(1));
+            Ok(Some(InternalEvent::Event(Event::FocusGained)))
+        }
+
+        #[cfg(feature = "event-stream")]
+        fn waker(&self) -> super::super::sys::Waker {
+            unimplemented!();
+        }
+    }
+
     #[derive(Default)]
-    struct FakeSource {
+    pub(crate) struct FakeSource {
         events: VecDeque<InternalEvent>,
         error: Option<ErrorKind>,
     }
@@ -412,7 +678,7 @@ mod tests {
             }
         }
 
-        fn with_events(events: &[InternalEvent]) -> FakeSource {
+        pub(crate) fn with_events(events: &[InternalEvent]) -> FakeSource {
             FakeSource {
                 events: events.to_vec().into(),
                 error: None,
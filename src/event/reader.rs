@@ -0,0 +1,927 @@
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+#[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+use std::os::unix::io::RawFd;
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    sync::Arc,
+    time::Duration,
+};
+
+use parking_lot::RwLock;
+
+#[cfg(windows)]
+use super::read::default_source;
+#[cfg(unix)]
+use super::source::unix::UnixInternalEventSource;
+#[cfg(all(unix, feature = "async-io"))]
+use super::AsyncIoEventStream;
+#[cfg(all(unix, feature = "tokio"))]
+use super::TokioEventStream;
+use super::{
+    filter::{EventFilter, Filter},
+    read::InternalEventReader,
+    recording::{read_recording, EventRecorder, ReplayMode},
+    source::replay::ReplayEventSource,
+    timeout::PollTimeout,
+    Event, InternalEvent,
+};
+#[cfg(feature = "event-stream")]
+use super::{sys::Waker, EventStream};
+#[cfg(unix)]
+use crate::terminal::SuspendModes;
+use crate::Result;
+
+/// A reader of [`Event`](enum.Event.html)s with its own event source.
+///
+/// The [`poll`](fn.poll.html) and [`read`](fn.read.html) functions use a global instance
+/// reading from the terminal. Use an `EventReader` if you need more independent input
+/// sources in one process.
+///
+/// Cloned readers share the same event source and queue of read events.
+///
+/// The terminal queries, like [`cursor::position`](../cursor/fn.position.html) or
+/// [`terminal::query_mode`](../terminal/fn.query_mode.html), always wait for the response with
+/// the global instance. An `EventReader` reading from the terminal hands the responses it reads
+/// to the global instance instead of reporting them, polling it during a query is fine.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use crossterm::{event::EventReader, Result};
+///
+/// fn print_events() -> Result<()> {
+///     let reader = EventReader::new()?;
+///
+///     loop {
+///         if reader.poll(Duration::from_millis(100))? {
+///             println!("{:?}", reader.read()?);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct EventReader {
+    inner: Arc<RwLock<InternalEventReader>>,
+}
+
+impl EventReader {
+    /// Constructs a new `EventReader` reading from the terminal.
+    pub fn new() -> Result<EventReader> {
+        // The responses of the queries are handed to the global reader
+        #[cfg(unix)]
+        return EventReader::with_signals(&[]);
+
+        #[cfg(windows)]
+        Ok(EventReader::from_internal_reader(InternalEventReader::new(
+            Some(default_source()?),
+        )))
+    }
+
+    /// Constructs a new `EventReader` reading from the terminal, which reports the given
+    /// signals as [`Event::Signal`](enum.Event.html#variant.Signal).
+    ///
+    /// `SIGWINCH` is always handled by the reader and reported as `Event::Resize`, see
+    /// [`set_suspend_handling`](#method.set_suspend_handling) to handle `SIGTSTP`. An error
+    /// is returned for the signals which can't be handled (`SIGKILL`, `SIGSTOP`, `SIGSEGV`, ...).
+    ///
+    /// **This method is available on Unix only.**
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use crossterm::{
+    ///     event::{Event, EventReader},
+    ///     Result,
+    /// };
+    ///
+    /// # #[cfg(unix)]
+    /// fn run() -> Result<()> {
+    ///     let reader = EventReader::with_signals(&[libc::SIGHUP, libc::SIGTERM])?;
+    ///
+    ///     loop {
+    ///         match reader.read()? {
+    ///             Event::Signal(libc::SIGHUP) => println!("Reloading"),
+    ///             Event::Signal(_) => return Ok(()),
+    ///             event => println!("{:?}", event),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[cfg(unix)]
+    pub fn with_signals(signals: &[i32]) -> Result<EventReader> {
+        Ok(EventReader::from_internal_reader(InternalEventReader::new(
+            Some(Box::new(UnixInternalEventSource::with_signals(signals)?)),
+        )))
+    }
+
+    /// Constructs a new `EventReader` decoding the input read from the given file descriptor.
+    ///
+    /// It can be a socket, a pipe, a pty master, ... The `fd` is kept alive as long as the
+    /// reader and no `Event::Resize` events are reported.
+    ///
+    /// The file descriptor should be in the non-blocking mode, otherwise the `poll` method
+    /// may block longer than the given timeout while waiting for the rest of an escape sequence.
+    ///
+    /// **This method is available on Unix only.**
+    #[cfg(unix)]
+    pub fn from_fd<T>(fd: T) -> Result<EventReader>
+    where
+        T: AsRawFd + Send + Sync + 'static,
+    {
+        Ok(EventReader::from_internal_reader(InternalEventReader::new(
+            Some(Box::new(UnixInternalEventSource::from_fd_owner(fd)?)),
+        )))
+    }
+
+    /// Constructs a new `EventReader` decoding the input read from the given reader.
+    ///
+    /// The input is read by a helper thread. No `Event::Resize` events are reported,
+    /// `Event::Hangup` is reported once the `reader` reaches the end.
+    ///
+    /// # Notes
+    ///
+    /// * The helper thread can't be interrupted while it waits for the input. Dropping all
+    ///   clones of this `EventReader` doesn't stop it, it exits once the `reader` returns
+    ///   more input, reaches the end or fails. A `reader` blocked in `read` (like an idle
+    ///   connection) is leaked with the thread until it returns more input or the end.
+    /// * Prefer the [`from_fd`](#method.from_fd) method for sockets, pipes and pty masters,
+    ///   it doesn't need a helper thread.
+    ///
+    /// **This method is available on Unix only.**
+    #[cfg(unix)]
+    pub fn from_reader<R>(reader: R) -> Result<EventReader>
+    where
+        R: Read + Send + 'static,
+    {
+        Ok(EventReader::from_internal_reader(InternalEventReader::new(
+            Some(Box::new(UnixInternalEventSource::from_reader(reader)?)),
+        )))
+    }
+
+    /// Constructs a new `EventReader` replaying the events of a recording.
+    ///
+    /// The whole recording is read up front, see [`EventRecorder`](struct.EventRecorder.html)
+    /// for its format. Once all events are replayed, the reader behaves like a terminal without
+    /// any input. No `Event::Resize` events are reported except the recorded ones.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use crossterm::{
+    ///     event::{EventReader, ReplayMode},
+    ///     Result,
+    /// };
+    ///
+    /// fn replay() -> Result<()> {
+    ///     let reader = EventReader::from_recording(File::open("events.txt")?, ReplayMode::RealTime)?;
+    ///
+    ///     loop {
+    ///         println!("{:?}", reader.read()?);
+    ///     }
+    /// }
+    /// ```
+    pub fn from_recording<R>(recording: R, mode: ReplayMode) -> Result<EventReader>
+    where
+        R: Read,
+    {
+        let source = ReplayEventSource::new(read_recording(recording)?, mode)?;
+        Ok(EventReader::from_internal_reader(InternalEventReader::new(
+            Some(Box::new(source)),
+        )))
+    }
+
+    pub(crate) fn from_internal_reader(reader: InternalEventReader) -> EventReader {
+        EventReader {
+            inner: Arc::new(RwLock::new(reader)),
+        }
+    }
+
+    /// Checks if there is an [`Event`](enum.Event.html) available.
+    ///
+    /// Returns `Ok(true)` if an [`Event`](enum.Event.html) is available otherwise it returns `Ok(false)`.
+    ///
+    /// `Ok(true)` guarantees that subsequent call to the [`read`](#method.read) method
+    /// wont block.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - maximum waiting time for event availability
+    pub fn poll(&self, timeout: Duration) -> Result<bool> {
+        self.poll_internal(Some(timeout), &EventFilter)
+    }
+
+    /// Reads a single [`Event`](enum.Event.html).
+    ///
+    /// This method blocks until an [`Event`](enum.Event.html) is available. Combine it with the
+    /// [`poll`](#method.poll) method to get non-blocking reads.
+    pub fn read(&self) -> Result<Event> {
+        match self.read_internal(&EventFilter)? {
+            InternalEvent::Event(event) => Ok(event),
+            #[cfg(unix)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Sets how long to wait for the rest of an escape sequence before a lone `ESC` byte
+    /// is reported as the `Esc` key.
+    ///
+    /// The default is zero, which reports `Esc` as soon as no more input is immediately
+    /// available. Slow connections can split sequences like `Alt+key` (`ESC` followed by
+    /// the key), a small timeout (like vim's `ttimeoutlen`) keeps them together.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish. It has no effect
+    /// on Windows.
+    pub fn set_escape_timeout(&self, timeout: Duration) {
+        self.inner.write().set_escape_timeout(timeout);
+    }
+
+    /// Sets whether input sequences which can't be decoded are reported as
+    /// [`Event::Unknown`](enum.Event.html#variant.Unknown) events instead of being dropped.
+    ///
+    /// It's disabled by default. Enable it to handle terminal specific sequences yourself
+    /// or to find out what a key the reader doesn't know sends.
+    ///
+    /// The OSC, DCS, APC, PM and SOS string sequences are collected until their BEL or ST
+    /// terminator and reported as well while it's enabled. An `Alt` key combination starting
+    /// the same way (`Alt+]`, `Alt+Shift+P`, ...) is reported only if no more input follows it.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish. It has no effect
+    /// on Windows.
+    pub fn set_report_unknown_sequences(&self, report: bool) {
+        self.inner.write().set_report_unknown_sequences(report);
+    }
+
+    /// Sets whether the SGR mouse reports are pixel positions, reported as
+    /// [`Event::PixelMouse`](enum.Event.html#variant.PixelMouse) instead of
+    /// [`Event::Mouse`](enum.Event.html#variant.Mouse).
+    ///
+    /// It's disabled by default. Enable it only if the terminal confirmed the
+    /// [`EnablePixelMouseCapture`](struct.EnablePixelMouseCapture.html) mode, the terminals
+    /// not supporting it report cell positions in the same format.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish. It has no effect
+    /// on Windows.
+    pub fn set_pixel_mouse_reports(&self, pixels: bool) {
+        self.inner.write().set_pixel_mouse_reports(pixels);
+    }
+
+    /// Registers a file descriptor to report its readiness for reading as
+    /// [`Event::Ready`](enum.Event.html#variant.Ready) with the given `token`.
+    ///
+    /// The readiness is reported once new input is available (edge-triggered), read
+    /// everything available before waiting for the next event. The file descriptor should
+    /// be in the non-blocking mode and it must stay open until it's deregistered with the
+    /// [`deregister_fd`](#method.deregister_fd) method. Readers replaying a recording
+    /// don't support file descriptors.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    ///
+    /// **This method is available on Unix only.**
+    #[cfg(unix)]
+    pub fn register_fd<T>(&self, source: &T, token: usize) -> Result<()>
+    where
+        T: AsRawFd,
+    {
+        self.inner.write().register_fd(source.as_raw_fd(), token)
+    }
+
+    /// Deregisters a file descriptor registered with the [`register_fd`](#method.register_fd)
+    /// method.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    ///
+    /// **This method is available on Unix only.**
+    #[cfg(unix)]
+    pub fn deregister_fd<T>(&self, source: &T) -> Result<()>
+    where
+        T: AsRawFd,
+    {
+        self.inner.write().deregister_fd(source.as_raw_fd())
+    }
+
+    /// Enables the handling of the `SIGTSTP` signal (`Ctrl+Z` outside of the raw mode,
+    /// `kill -TSTP`), `None` disables it.
+    ///
+    /// The signal isn't handled by default. Once enabled, the raw mode and the given `modes`
+    /// are restored and the process is stopped right away, even if the application isn't
+    /// reading events at the moment. The terminal is reapplied when the shell continues the
+    /// process and this reader reports [`Event::Suspend`](enum.Event.html#variant.Suspend)
+    /// followed by [`Event::Resume`](enum.Event.html#variant.Resume), redraw the screen then.
+    ///
+    /// The terminal is shared by the whole process, the `modes` of the last call apply to
+    /// all the readers handling the signal. Call this method again whenever the application
+    /// enables or disables any of the modes. The previous action of the signal is restored
+    /// once no reader handles it. Readers replaying a recording don't support it.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    ///
+    /// **This method is available on Unix only.**
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::{stdout, Write};
+    ///
+    /// use crossterm::{
+    ///     event::{EnableMouseCapture, Event, EventReader, MouseTrackingMode},
+    ///     execute,
+    ///     terminal::{enable_raw_mode, EnterAlternateScreen, SuspendModes},
+    ///     Result,
+    /// };
+    ///
+    /// # #[cfg(unix)]
+    /// fn run() -> Result<()> {
+    ///     let reader = EventReader::new()?;
+    ///
+    ///     enable_raw_mode()?;
+    ///     execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    ///     reader.set_suspend_handling(Some(SuspendModes {
+    ///         alternate_screen: true,
+    ///         mouse_capture: Some(MouseTrackingMode::ButtonEvent),
+    ///         ..SuspendModes::default()
+    ///     }))?;
+    ///
+    ///     loop {
+    ///         match reader.read()? {
+    ///             Event::Resume => { /* redraw */ }
+    ///             event => println!("{:?}", event),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[cfg(unix)]
+    pub fn set_suspend_handling(&self, modes: Option<SuspendModes>) -> Result<()> {
+        self.inner.write().set_suspend_handling(modes)
+    }
+
+    /// Adds a one-shot timer reported as [`Event::Tick`](enum.Event.html#variant.Tick) with
+    /// the given `id` after the `delay`.
+    ///
+    /// A timer with the same `id` is replaced. Ticks are reported by the `poll` and `read`
+    /// methods, they aren't recorded.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    pub fn add_timer(&self, id: usize, delay: Duration) {
+        self.inner.write().add_timer(id, delay, None);
+    }
+
+    /// Adds a periodic timer reported as [`Event::Tick`](enum.Event.html#variant.Tick) with
+    /// the given `id` every `interval`.
+    ///
+    /// A timer with the same `id` is replaced. Missed ticks, when events aren't read in time,
+    /// are reported just once and the next one is scheduled one `interval` after it. A due
+    /// tick is reported right after the next event read from the terminal, neither the input
+    /// nor the ticks are starved.
+    ///
+    /// Returns an error if the `interval` is zero.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    pub fn add_interval(&self, id: usize, interval: Duration) -> Result<()> {
+        if interval == Duration::from_secs(0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The interval of a periodic timer can't be zero.",
+            )
+            .into());
+        }
+
+        self.inner.write().add_timer(id, interval, Some(interval));
+        Ok(())
+    }
+
+    /// Removes a timer added with the [`add_timer`](#method.add_timer) or
+    /// [`add_interval`](#method.add_interval) method, returns `false` if there's no timer
+    /// with the given `id`.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    pub fn remove_timer(&self, id: usize) -> bool {
+        self.inner.write().remove_timer(id)
+    }
+
+    /// Starts recording all the [`Event`](enum.Event.html)s read by this reader to the `writer`.
+    ///
+    /// The events are written as soon as they are read from the terminal, including the ones
+    /// not consumed yet, see [`EventRecorder`](struct.EventRecorder.html) for the format. Any
+    /// previous recording is stopped.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    pub fn record<W>(&self, writer: W) -> Result<()>
+    where
+        W: Write + Send + Sync + 'static,
+    {
+        let recorder = EventRecorder::new(Box::new(writer) as Box<dyn Write + Send + Sync>)?;
+        self.inner.write().set_recorder(Some(recorder));
+        Ok(())
+    }
+
+    /// Stops recording the events started with the [`record`](#method.record) method and
+    /// drops the writer.
+    ///
+    /// An event which can't be written is still reported, but the recording stops right away
+    /// and this method returns the write error.
+    ///
+    /// This method waits for any pending `poll` or `read` call to finish.
+    pub fn stop_recording(&self) -> Result<()> {
+        let mut inner = self.inner.write();
+        let result = inner.recording_result();
+        inner.set_recorder(None);
+        result
+    }
+
+    /// Returns a stream of the [`Event`](enum.Event.html)s read by this reader.
+    ///
+    /// **This method is not available by default. You have to use the `event-stream` feature flag
+    /// to make it available.**
+    #[cfg(feature = "event-stream")]
+    pub fn stream(&self) -> EventStream {
+        EventStream::with_reader(self.clone())
+    }
+
+    /// Returns a stream of the [`Event`](enum.Event.html)s read by this reader, polled by
+    /// the `tokio` reactor.
+    ///
+    /// It must be called within the tokio runtime, see
+    /// [`TokioEventStream`](struct.TokioEventStream.html).
+    ///
+    /// **This method is not available by default. You have to use the `tokio` feature flag
+    /// to make it available. It's available on Unix only.**
+    #[cfg(all(unix, feature = "tokio"))]
+    pub fn tokio_stream(&self) -> Result<TokioEventStream> {
+        TokioEventStream::with_reader(self.clone())
+    }
+
+    /// Returns a stream of the [`Event`](enum.Event.html)s read by this reader, polled by
+    /// the `async-io` reactor.
+    ///
+    /// See [`AsyncIoEventStream`](struct.AsyncIoEventStream.html).
+    ///
+    /// **This method is not available by default. You have to use the `async-io` feature flag
+    /// to make it available. It's available on Unix only.**
+    #[cfg(all(unix, feature = "async-io"))]
+    pub fn async_io_stream(&self) -> Result<AsyncIoEventStream> {
+        AsyncIoEventStream::with_reader(self.clone())
+    }
+
+    /// Polls to check if there are any `InternalEvent`s that can be read within the given duration.
+    pub(crate) fn poll_internal<F>(&self, timeout: Option<Duration>, filter: &F) -> Result<bool>
+    where
+        F: Filter,
+    {
+        let (mut reader, timeout) = if let Some(timeout) = timeout {
+            let poll_timeout = PollTimeout::new(Some(timeout));
+            if let Some(reader) = self.inner.try_write_for(timeout) {
+                (reader, poll_timeout.leftover())
+            } else {
+                return Ok(false);
+            }
+        } else {
+            (self.inner.write(), None)
+        };
+        reader.poll(timeout, filter)
+    }
+
+    /// Reads a single `InternalEvent`.
+    pub(crate) fn read_internal<F>(&self, filter: &F) -> Result<InternalEvent>
+    where
+        F: Filter,
+    {
+        let mut reader = self.inner.write();
+        reader.read(filter)
+    }
+
+    /// Returns a `Waker` allowing to wake/force the `poll` method to return `Ok(false)`.
+    #[cfg(feature = "event-stream")]
+    pub(crate) fn waker(&self) -> Waker {
+        self.inner.write().waker()
+    }
+
+    /// Returns a file descriptor which is readable when the source may have a new event.
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    pub(crate) fn raw_fd(&self) -> Result<RawFd> {
+        self.inner.read().raw_fd()
+    }
+
+    /// Returns the time left before an event is available without any new input, `None`
+    /// if it depends on the input only or if the reader is used by another thread.
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    pub(crate) fn pending_leftover(&self) -> Option<Duration> {
+        self.inner.try_read()?.pending_leftover()
+    }
+}
+
+impl fmt::Debug for EventReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventReader").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, Write},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    #[cfg(unix)]
+    use std::os::unix::net::UnixStream;
+
+    #[cfg(unix)]
+    use super::super::query::PENDING_QUERIES;
+    use super::{
+        super::{
+            read::tests::FakeSource, Event, InternalEvent, KeyCode, KeyEvent, KeyModifiers,
+            MouseButton, MouseEvent, ReplayMode,
+        },
+        EventReader, InternalEventReader,
+    };
+
+    fn reader_with_events(events: &[InternalEvent]) -> EventReader {
+        EventReader::from_internal_reader(InternalEventReader::new(Some(Box::new(
+            FakeSource::with_events(events),
+        ))))
+    }
+
+    #[test]
+    fn test_readers_are_independent() {
+        let first =
+            reader_with_events(&[InternalEvent::Event(Event::Key(KeyCode::Char('a').into()))]);
+        let second =
+            reader_with_events(&[InternalEvent::Event(Event::Key(KeyCode::Char('b').into()))]);
+
+        assert!(second.poll(Duration::from_secs(0)).unwrap());
+        assert_eq!(
+            second.read().unwrap(),
+            Event::Key(KeyCode::Char('b').into())
+        );
+        assert!(!second.poll(Duration::from_secs(0)).unwrap());
+
+        assert_eq!(first.read().unwrap(), Event::Key(KeyCode::Char('a').into()));
+    }
+
+    #[test]
+    fn test_cloned_readers_share_source() {
+        let reader = reader_with_events(&[
+            InternalEvent::Event(Event::Key(KeyCode::Char('a').into())),
+            InternalEvent::Event(Event::Key(KeyCode::Char('b').into())),
+        ]);
+        let clone = reader.clone();
+
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('a').into())
+        );
+        assert_eq!(clone.read().unwrap(), Event::Key(KeyCode::Char('b').into()));
+        assert!(!reader.poll(Duration::from_secs(0)).unwrap());
+    }
+
+    #[test]
+    fn test_timers() {
+        let reader = reader_with_events(&[InternalEvent::Event(Event::FocusGained)]);
+        reader.add_interval(1, Duration::from_millis(20)).unwrap();
+        reader.add_timer(2, Duration::from_millis(30));
+
+        assert_eq!(reader.read().unwrap(), Event::FocusGained);
+        assert_eq!(reader.read().unwrap(), Event::Tick(1));
+        assert_eq!(reader.read().unwrap(), Event::Tick(2));
+        assert_eq!(reader.read().unwrap(), Event::Tick(1));
+
+        assert!(reader.remove_timer(1));
+        assert!(!reader.remove_timer(2));
+        assert!(!reader.poll(Duration::from_millis(50)).unwrap());
+
+        assert!(reader.add_interval(1, Duration::from_secs(0)).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_from_reader() {
+        let reader = EventReader::from_reader(&b"a\x1B[A"[..]).unwrap();
+
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('a').into())
+        );
+        assert_eq!(reader.read().unwrap(), Event::Key(KeyCode::Up.into()));
+        assert_eq!(reader.read().unwrap(), Event::Hangup);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_from_fd() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+
+        assert!(!reader.poll(Duration::from_millis(10)).unwrap());
+
+        client.write_all(b"\x1B[B").unwrap();
+        assert!(reader.poll(Duration::from_secs(1)).unwrap());
+        assert_eq!(reader.read().unwrap(), Event::Key(KeyCode::Down.into()));
+    }
+
+    #[cfg(all(unix, feature = "tokio"))]
+    #[test]
+    fn test_tokio_stream() {
+        use futures::StreamExt;
+
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let mut stream = reader.tokio_stream().unwrap();
+
+            client.write_all(b"a\x1B").unwrap();
+            let event = stream.next().await.unwrap().unwrap();
+            assert_eq!(event, Event::Key(KeyCode::Char('a').into()));
+            // Reported after the escape timeout without any new input
+            let event = stream.next().await.unwrap().unwrap();
+            assert_eq!(event, Event::Key(KeyCode::Esc.into()));
+        });
+    }
+
+    #[cfg(all(unix, feature = "async-io"))]
+    #[test]
+    fn test_async_io_stream() {
+        use futures::{executor::block_on, StreamExt};
+
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+        reader.add_timer(1, Duration::from_millis(10));
+        let mut stream = reader.async_io_stream().unwrap();
+
+        block_on(async {
+            assert_eq!(stream.next().await.unwrap().unwrap(), Event::Tick(1));
+
+            client.write_all(b"\x1B[A").unwrap();
+            let event = stream.next().await.unwrap().unwrap();
+            assert_eq!(event, Event::Key(KeyCode::Up.into()));
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_escape_timeout_joins_split_sequence() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+        reader.set_escape_timeout(Duration::from_millis(200));
+
+        client.write_all(b"\x1B").unwrap();
+        assert!(!reader.poll(Duration::from_millis(10)).unwrap());
+
+        client.write_all(b"x").unwrap();
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_escape_timeout_reports_esc() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+        reader.set_escape_timeout(Duration::from_millis(20));
+
+        client.write_all(b"\x1B").unwrap();
+        assert_eq!(reader.read().unwrap(), Event::Key(KeyCode::Esc.into()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_report_unknown_sequences() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+
+        client.write_all(b"\x1B[999zx").unwrap();
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('x').into())
+        );
+
+        reader.set_report_unknown_sequences(true);
+        client.write_all(b"\x1B[999z\x1B[?1ux").unwrap();
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Unknown(b"\x1B[999z".to_vec())
+        );
+        assert_eq!(reader.read().unwrap(), Event::Unknown(b"\x1B[?1u".to_vec()));
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('x').into())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_report_unknown_string_sequences() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+        reader.set_report_unknown_sequences(true);
+
+        client.write_all(b"\x1B]52;c;aGVsbG8=\x07x").unwrap();
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Unknown(b"\x1B]52;c;aGVsbG8=\x07".to_vec())
+        );
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('x').into())
+        );
+
+        client.write_all(b"\x1BP1$r0m\x1B\\").unwrap();
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Unknown(b"\x1BP1$r0m\x1B\\".to_vec())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_query_of_another_reader() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+
+        // Only the terminal readers parse the string sequences during a query
+        PENDING_QUERIES.fetch_add(1, Ordering::SeqCst);
+        client.write_all(b"\x1B]x").unwrap();
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyEvent::new(KeyCode::Char(']'), KeyModifiers::ALT))
+        );
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('x').into())
+        );
+        PENDING_QUERIES.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_escape_timeout_reports_alt_key() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+        reader.set_escape_timeout(Duration::from_millis(20));
+
+        client.write_all(b"\x1B]").unwrap();
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyEvent::new(KeyCode::Char(']'), KeyModifiers::ALT))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_alt_keys_followed_by_typing() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+        let alt = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT));
+
+        // Alt+Shift+P, Alt+] and the typing after them aren't swallowed as the DCS & OSC
+        // string sequences
+        client.write_all(b"\x1BPa\x1B]bc").unwrap();
+        assert_eq!(reader.read().unwrap(), alt('P'));
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('a').into())
+        );
+        assert_eq!(reader.read().unwrap(), alt(']'));
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('b').into())
+        );
+        assert_eq!(
+            reader.read().unwrap(),
+            Event::Key(KeyCode::Char('c').into())
+        );
+
+        client.write_all(b"\x1BP\x1BP\x1BP").unwrap();
+        for _ in 0..3 {
+            assert_eq!(reader.read().unwrap(), alt('P'));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pixel_mouse_reports() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let reader = EventReader::from_fd(server).unwrap();
+        let event = MouseEvent::Down(MouseButton::Left, 1233, 566, KeyModifiers::empty());
+
+        client.write_all(b"\x1B[<0;1234;567M").unwrap();
+        assert_eq!(reader.read().unwrap(), Event::Mouse(event));
+
+        reader.set_pixel_mouse_reports(true);
+        client.write_all(b"\x1B[<0;1234;567M").unwrap();
+        assert_eq!(reader.read().unwrap(), Event::PixelMouse(event));
+
+        // `DisableMouseCapture` followed by the cell positions again
+        reader.set_pixel_mouse_reports(false);
+        client.write_all(b"\x1B[<0;1234;567M").unwrap();
+        assert_eq!(reader.read().unwrap(), Event::Mouse(event));
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let events = [
+            Event::Key(KeyCode::Char('a').into()),
+            Event::Resize(10, 10),
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        ];
+        let reader = reader_with_events(&[
+            InternalEvent::Event(events[0].clone()),
+            InternalEvent::Event(events[1].clone()),
+            InternalEvent::Event(events[2].clone()),
+        ]);
+        let buffer = SharedBuffer::default();
+
+        assert_eq!(reader.read().unwrap(), events[0]);
+        reader.record(buffer.clone()).unwrap();
+        assert_eq!(reader.read().unwrap(), events[1]);
+        reader.stop_recording().unwrap();
+        assert_eq!(reader.read().unwrap(), events[2]);
+
+        let recording = buffer.0.lock().unwrap().clone();
+        let replay = EventReader::from_recording(&recording[..], ReplayMode::RealTime).unwrap();
+
+        assert_eq!(replay.read().unwrap(), events[1]);
+        assert!(!replay.poll(Duration::from_millis(10)).unwrap());
+    }
+
+    struct FailingWriter(Arc<AtomicBool>);
+
+    impl Write for FailingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0.load(Ordering::SeqCst) {
+                Err(io::Error::other("failed"))
+            } else {
+                Ok(buf.len())
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_recording_error_keeps_events() {
+        let events = [
+            Event::Key(KeyCode::Char('a').into()),
+            Event::Key(KeyCode::Char('b').into()),
+        ];
+        let reader = reader_with_events(&[
+            InternalEvent::Event(events[0].clone()),
+            InternalEvent::Event(events[1].clone()),
+        ]);
+        let fail = Arc::new(AtomicBool::new(false));
+
+        reader.record(FailingWriter(fail.clone())).unwrap();
+        fail.store(true, Ordering::SeqCst);
+
+        assert_eq!(reader.read().unwrap(), events[0]);
+        assert_eq!(reader.read().unwrap(), events[1]);
+        assert!(reader.stop_recording().is_err());
+        assert!(reader.stop_recording().is_ok());
+    }
+
+    #[test]
+    fn test_replay_invalid_recording() {
+        assert!(
+            EventReader::from_recording(&b"crossterm-events 1\nx"[..], ReplayMode::RealTime)
+                .is_err()
+        );
+    }
+}
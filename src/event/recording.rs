@@ -0,0 +1,584 @@
+//! Recording and replay of events.
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+    time::{Duration, Instant},
+};
+
+use crate::Result;
+
+use super::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEvent,
+    KEY_EVENT_STATE_NAMES,
+};
+
+/// The first line of every recording.
+const HEADER: &str = "crossterm-events 1";
+
+/// Represents how fast the events of a recording are replayed.
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ReplayMode {
+    /// Each event is available after the recorded delay since the previous one.
+    RealTime,
+    /// All events are available immediately.
+    AsFastAsPossible,
+}
+
+/// Writes [`Event`](enum.Event.html)s with the time elapsed between them to a recording.
+///
+/// Use [`EventReader::record`](struct.EventReader.html#method.record) to record all the events
+/// read by a reader or the `EventRecorder` directly to build a recording yourself.
+///
+/// # Format
+///
+/// A recording is a text file starting with the `crossterm-events 1` header line followed
+/// by one line per event, the delay since the previous event in microseconds and the event:
+///
+/// ```text
+/// crossterm-events 1
+/// 0 resize 80 24
+/// 120500 key none a press none
+/// 80250 key ctrl c press none
+/// 1000000 mouse down left 10 5 none
+/// 15000 paste hello\nworld
+/// ```
+///
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{fs::File, time::Duration};
+///
+/// use crossterm::{
+///     event::{Event, EventRecorder, KeyCode},
+///     Result,
+/// };
+///
+/// fn write_recording() -> Result<()> {
+///     let mut recorder = EventRecorder::new(File::create("events.txt")?)?;
+///
+///     recorder.record_with_delay(Duration::from_secs(0), &Event::Resize(80, 24))?;
+///     recorder.record_with_delay(Duration::from_millis(100), &Event::Key(KeyCode::Enter.into()))?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct EventRecorder<W: Write> {
+    writer: W,
+    last: Instant,
+}
+
+impl<W: Write> EventRecorder<W> {
+    /// Constructs a new `EventRecorder` and writes the recording header to the `writer`.
+    pub fn new(mut writer: W) -> Result<EventRecorder<W>> {
+        writeln!(writer, "{}", HEADER)?;
+        writer.flush()?;
+
+        Ok(EventRecorder {
+            writer,
+            last: Instant::now(),
+        })
+    }
+
+    /// Records the event with the time elapsed since the previous one (or since the recorder
+    /// was constructed).
+    pub fn record(&mut self, event: &Event) -> Result<()> {
+        let now = Instant::now();
+        let delay = now - self.last;
+        self.last = now;
+        self.record_with_delay(delay, event)
+    }
+
+    /// Records the event with the given delay since the previous one.
+    pub fn record_with_delay(&mut self, delay: Duration, event: &Event) -> Result<()> {
+        writeln!(
+            self.writer,
+            "{} {}",
+            delay.as_micros(),
+            RecordedEvent(event)
+        )?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads all the events of a recording with their delays.
+pub(crate) fn read_recording<R: Read>(reader: R) -> Result<Vec<(Duration, Event)>> {
+    let mut events = Vec::new();
+    let mut header = false;
+
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim_end_matches('\r');
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if !header {
+            if trimmed != HEADER {
+                return Err(invalid_recording(
+                    index,
+                    "missing `crossterm-events 1` header",
+                ));
+            }
+            header = true;
+            continue;
+        }
+
+        match parse_line(trimmed) {
+            Some(event) => events.push(event),
+            None => return Err(invalid_recording(index, "invalid event")),
+        }
+    }
+
+    if !header {
+        return Err(invalid_recording(0, "missing `crossterm-events 1` header"));
+    }
+
+    Ok(events)
+}
+
+fn invalid_recording(index: usize, reason: &str) -> crate::ErrorKind {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid event recording, line {}: {}.", index + 1, reason),
+    )
+    .into()
+}
+
+/// Formats an event of a recording line.
+struct RecordedEvent<'a>(&'a Event);
+
+impl fmt::Display for RecordedEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Event::FocusGained => f.write_str("focus-gained"),
+            Event::FocusLost => f.write_str("focus-lost"),
+            Event::Suspend => f.write_str("suspend"),
+            Event::Resume => f.write_str("resume"),
+            Event::Hangup => f.write_str("hangup"),
+            Event::Ready(token) => write!(f, "ready {}", token),
+            Event::Tick(id) => write!(f, "tick {}", id),
+            Event::Signal(signal) => write!(f, "signal {}", signal),
+            Event::Key(event) => {
+                write!(f, "key {} ", event.modifiers)?;
+                match event.code {
+                    KeyCode::Char(c) if c != ' ' && (c.is_whitespace() || c.is_control()) => {
+                        write!(f, "u+{:x}", c as u32)?
+                    }
+                    code => write!(f, "{}", code)?,
+                }
+                let kind = match event.kind {
+                    KeyEventKind::Press => "press",
+                    KeyEventKind::Repeat => "repeat",
+                    KeyEventKind::Release => "release",
+                };
+                write!(f, " {} ", kind)?;
+                format_key_event_state(event.state, f)
+            }
+            Event::Mouse(event) | Event::PixelMouse(event) => {
+                let (kind, button, column, row, modifiers) = match *event {
+                    MouseEvent::Down(button, column, row, modifiers) => {
+                        ("down", Some(button), column, row, modifiers)
+                    }
+                    MouseEvent::Up(button, column, row, modifiers) => {
+                        ("up", Some(button), column, row, modifiers)
+                    }
+                    MouseEvent::Drag(button, column, row, modifiers) => {
+                        ("drag", Some(button), column, row, modifiers)
+                    }
+                    MouseEvent::Moved(column, row, modifiers) => {
+                        ("moved", None, column, row, modifiers)
+                    }
+                    MouseEvent::ScrollDown(column, row, modifiers) => {
+                        ("scrolldown", None, column, row, modifiers)
+                    }
+                    MouseEvent::ScrollUp(column, row, modifiers) => {
+                        ("scrollup", None, column, row, modifiers)
+                    }
+                    MouseEvent::ScrollLeft(column, row, modifiers) => {
+                        ("scrollleft", None, column, row, modifiers)
+                    }
+                    MouseEvent::ScrollRight(column, row, modifiers) => {
+                        ("scrollright", None, column, row, modifiers)
+                    }
+                };
+                let name = match self.0 {
+                    Event::PixelMouse(_) => "pixel-mouse",
+                    _ => "mouse",
+                };
+                write!(f, "{} {}", name, kind)?;
+                match button {
+                    Some(MouseButton::Left) => f.write_str(" left")?,
+                    Some(MouseButton::Right) => f.write_str(" right")?,
+                    Some(MouseButton::Middle) => f.write_str(" middle")?,
+                    Some(MouseButton::Back) => f.write_str(" back")?,
+                    Some(MouseButton::Forward) => f.write_str(" forward")?,
+                    Some(MouseButton::Other(button)) => write!(f, " button{}", button)?,
+                    None => {}
+                }
+                write!(f, " {} {} {}", column, row, modifiers)
+            }
+            Event::Paste(text) => {
+                f.write_str("paste ")?;
+                for c in text.chars() {
+                    match c {
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32)?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                Ok(())
+            }
+            Event::Resize(columns, rows) => write!(f, "resize {} {}", columns, rows),
+            Event::Unknown(bytes) => {
+                f.write_str("unknown ")?;
+                for byte in bytes {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn format_key_event_state(state: KeyEventState, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if state.is_empty() {
+        return f.write_str("none");
+    }
+
+    let mut first = true;
+    for (flag, name) in KEY_EVENT_STATE_NAMES.iter() {
+        if state.contains(*flag) {
+            if !first {
+                f.write_str("-")?;
+            }
+            f.write_str(name)?;
+            first = false;
+        }
+    }
+    Ok(())
+}
+
+fn parse_key_event_state(src: &str) -> Option<KeyEventState> {
+    if src == "none" {
+        return Some(KeyEventState::NONE);
+    }
+
+    src.split('-').try_fold(KeyEventState::NONE, |state, name| {
+        KEY_EVENT_STATE_NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(flag, _)| state | *flag)
+    })
+}
+
+/// Parses a recording line, the delay and the event.
+fn parse_line(line: &str) -> Option<(Duration, Event)> {
+    let (delay, event) = split_word(line);
+    let delay = Duration::from_micros(delay.parse().ok()?);
+    let (kind, args) = split_word(event);
+
+    let event = match kind {
+        "focus-gained" if args.is_empty() => Event::FocusGained,
+        "focus-lost" if args.is_empty() => Event::FocusLost,
+        "suspend" if args.is_empty() => Event::Suspend,
+        "resume" if args.is_empty() => Event::Resume,
+        "hangup" if args.is_empty() => Event::Hangup,
+        "ready" => Event::Ready(args.parse().ok()?),
+        "tick" => Event::Tick(args.parse().ok()?),
+        "signal" => Event::Signal(args.parse().ok()?),
+        "key" => {
+            let mut args = args.split(' ');
+            let modifiers: KeyModifiers = args.next()?.parse().ok()?;
+            let code = args.next()?;
+            let code = if code.len() > 2 && code.starts_with("u+") {
+                KeyCode::Char(std::char::from_u32(
+                    u32::from_str_radix(&code[2..], 16).ok()?,
+                )?)
+            } else {
+                code.parse().ok()?
+            };
+            let kind = match args.next()? {
+                "press" => KeyEventKind::Press,
+                "repeat" => KeyEventKind::Repeat,
+                "release" => KeyEventKind::Release,
+                _ => return None,
+            };
+            let state = parse_key_event_state(args.next()?)?;
+            if args.next().is_some() {
+                return None;
+            }
+            Event::Key(KeyEvent::new_with_kind_and_state(
+                code, modifiers, kind, state,
+            ))
+        }
+        "mouse" | "pixel-mouse" => {
+            let pixels = kind == "pixel-mouse";
+            let mut args = args.split(' ');
+            let kind = args.next()?;
+            let button = match kind {
+                "down" | "up" | "drag" => Some(match args.next()? {
+                    "left" => MouseButton::Left,
+                    "right" => MouseButton::Right,
+                    "middle" => MouseButton::Middle,
+                    "back" => MouseButton::Back,
+                    "forward" => MouseButton::Forward,
+                    button if button.starts_with("button") => {
+                        MouseButton::Other(button[6..].parse().ok()?)
+                    }
+                    _ => return None,
+                }),
+                _ => None,
+            };
+            let column = args.next()?.parse().ok()?;
+            let row = args.next()?.parse().ok()?;
+            let modifiers: KeyModifiers = args.next()?.parse().ok()?;
+            if args.next().is_some() {
+                return None;
+            }
+            let event = match (kind, button) {
+                ("down", Some(button)) => MouseEvent::Down(button, column, row, modifiers),
+                ("up", Some(button)) => MouseEvent::Up(button, column, row, modifiers),
+                ("drag", Some(button)) => MouseEvent::Drag(button, column, row, modifiers),
+                ("moved", _) => MouseEvent::Moved(column, row, modifiers),
+                ("scrolldown", _) => MouseEvent::ScrollDown(column, row, modifiers),
+                ("scrollup", _) => MouseEvent::ScrollUp(column, row, modifiers),
+                ("scrollleft", _) => MouseEvent::ScrollLeft(column, row, modifiers),
+                ("scrollright", _) => MouseEvent::ScrollRight(column, row, modifiers),
+                _ => return None,
+            };
+            if pixels {
+                Event::PixelMouse(event)
+            } else {
+                Event::Mouse(event)
+            }
+        }
+        "paste" => Event::Paste(unescape(args)?),
+        "resize" => {
+            let (columns, rows) = split_word(args);
+            Event::Resize(columns.parse().ok()?, rows.parse().ok()?)
+        }
+        "unknown" if args.len() % 2 == 0 && args.is_ascii() => Event::Unknown(
+            (0..args.len())
+                .step_by(2)
+                .map(|idx| u8::from_str_radix(&args[idx..idx + 2], 16).ok())
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    };
+
+    Some((delay, event))
+}
+
+/// Splits the first space separated word.
+fn split_word(src: &str) -> (&str, &str) {
+    match src.find(' ') {
+        Some(idx) => (&src[..idx], &src[idx + 1..]),
+        None => (src, ""),
+    }
+}
+
+/// Reverts the escaping of the pasted text.
+fn unescape(src: &str) -> Option<String> {
+    let mut result = String::with_capacity(src.len());
+    let mut chars = src.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            '\\' => result.push('\\'),
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let mut code = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        c => code.push(c),
+                    }
+                }
+                result.push(std::char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+            _ => return None,
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        super::{
+            Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MediaKeyCode,
+            MouseButton, MouseEvent,
+        },
+        read_recording, EventRecorder,
+    };
+
+    #[test]
+    fn test_recording_round_trip() {
+        let events = vec![
+            (Duration::from_secs(0), Event::Resize(80, 24)),
+            (Duration::from_micros(120_500), Event::FocusGained),
+            (
+                Duration::from_micros(5),
+                Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            ),
+            (
+                Duration::from_micros(5),
+                Event::Key(KeyEvent::new_with_kind_and_state(
+                    KeyCode::Char('A'),
+                    KeyModifiers::NONE,
+                    KeyEventKind::Release,
+                    KeyEventState::CAPS_LOCK | KeyEventState::KEYPAD,
+                )),
+            ),
+            (
+                Duration::from_micros(5),
+                Event::Key(KeyCode::Char(' ').into()),
+            ),
+            (
+                Duration::from_micros(5),
+                Event::Key(KeyCode::Char('\n').into()),
+            ),
+            (
+                Duration::from_micros(5),
+                Event::Key(KeyCode::Char('-').into()),
+            ),
+            (
+                Duration::from_micros(5),
+                Event::Key(KeyCode::Media(MediaKeyCode::PlayPause).into()),
+            ),
+            (
+                Duration::from_secs(1),
+                Event::Mouse(MouseEvent::Drag(
+                    MouseButton::Middle,
+                    10,
+                    5,
+                    KeyModifiers::SHIFT | KeyModifiers::ALT,
+                )),
+            ),
+            (
+                Duration::from_secs(1),
+                Event::Mouse(MouseEvent::Up(
+                    MouseButton::Other(10),
+                    10,
+                    5,
+                    KeyModifiers::NONE,
+                )),
+            ),
+            (
+                Duration::from_secs(1),
+                Event::Mouse(MouseEvent::ScrollLeft(0, 0, KeyModifiers::NONE)),
+            ),
+            (
+                Duration::from_secs(1),
+                Event::PixelMouse(MouseEvent::Down(
+                    MouseButton::Left,
+                    640,
+                    0,
+                    KeyModifiers::NONE,
+                )),
+            ),
+            (
+                Duration::from_secs(2),
+                Event::Paste("a\\b\nc\r\td\u{7}é ".to_string()),
+            ),
+            (Duration::from_secs(2), Event::Paste(String::new())),
+            (Duration::from_secs(3), Event::FocusLost),
+            (Duration::from_secs(3), Event::Suspend),
+            (Duration::from_secs(60), Event::Resume),
+            (Duration::from_secs(60), Event::Signal(10)),
+            (Duration::from_secs(60), Event::Ready(3)),
+            (Duration::from_secs(60), Event::Tick(7)),
+            (Duration::from_secs(60), Event::Hangup),
+            (
+                Duration::from_secs(3),
+                Event::Unknown(b"\x1B]11;rgb:0000/0000/0000\x07".to_vec()),
+            ),
+        ];
+
+        let mut recorder = EventRecorder::new(Vec::new()).unwrap();
+        for (delay, event) in &events {
+            recorder.record_with_delay(*delay, event).unwrap();
+        }
+        let recording = recorder.into_inner();
+
+        assert_eq!(read_recording(&recording[..]).unwrap(), events);
+    }
+
+    #[test]
+    fn test_recording_format() {
+        let mut recorder = EventRecorder::new(Vec::new()).unwrap();
+        recorder
+            .record_with_delay(
+                Duration::from_millis(100),
+                &Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            )
+            .unwrap();
+        recorder
+            .record_with_delay(
+                Duration::from_millis(1),
+                &Event::Mouse(MouseEvent::Down(
+                    MouseButton::Left,
+                    10,
+                    5,
+                    KeyModifiers::NONE,
+                )),
+            )
+            .unwrap();
+
+        assert_eq!(
+            recorder.into_inner(),
+            &b"crossterm-events 1\n100000 key ctrl c press none\n1000 mouse down left 10 5 none\n"
+                [..]
+        );
+    }
+
+    #[test]
+    fn test_read_recording_skips_comments() {
+        let recording = b"# bug #123\n\ncrossterm-events 1\r\n# resized\n10 resize 80 24\n";
+
+        assert_eq!(
+            read_recording(&recording[..]).unwrap(),
+            vec![(Duration::from_micros(10), Event::Resize(80, 24))]
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_recording() {
+        assert!(read_recording(&b""[..]).is_err());
+        assert!(read_recording(&b"10 resize 80 24\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 2\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 1\nresize 80 24\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 1\n10 resize 80\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 1\n10 key none a press\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 1\n10 mouse down 1 1 none\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 1\n10 paste a\\qb\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 1\n10 focus-lost now\n"[..]).is_err());
+        assert!(read_recording(&b"crossterm-events 1\n10 unknown 1b5\n"[..]).is_err());
+    }
+}
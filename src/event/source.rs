@@ -1,9 +1,15 @@
 use std::time::Duration;
+#[cfg(unix)]
+use std::{io, os::unix::io::RawFd};
+
+#[cfg(unix)]
+use crate::terminal::SuspendModes;
 
 #[cfg(feature = "event-stream")]
 use super::sys::Waker;
 use super::InternalEvent;
 
+pub(crate) mod replay;
 #[cfg(unix)]
 pub(crate) mod unix;
 #[cfg(windows)]
@@ -24,4 +30,60 @@ pub(crate) trait EventSource: Sync + Send {
     /// Returns a `Waker` allowing to wake/force the `try_read` method to return `Ok(None)`.
     #[cfg(feature = "event-stream")]
     fn waker(&self) -> Waker;
+
+    /// Sets how long to wait for the rest of an escape sequence before a lone `ESC` byte
+    /// is reported as the `Esc` key.
+    ///
+    /// Sources which don't parse escape sequences ignore it.
+    fn set_escape_timeout(&mut self, _timeout: Duration) {}
+
+    /// Sets whether input sequences which can't be parsed are reported as `Event::Unknown`.
+    ///
+    /// Sources which don't parse escape sequences ignore it.
+    fn set_report_unknown_sequences(&mut self, _report: bool) {}
+
+    /// Sets whether the SGR mouse reports are pixel positions reported as `Event::PixelMouse`.
+    ///
+    /// Sources which don't parse escape sequences ignore it.
+    fn set_pixel_mouse_reports(&mut self, _pixels: bool) {}
+
+    /// Registers the file descriptor to report its readiness for reading as `Event::Ready`
+    /// with the given token.
+    #[cfg(unix)]
+    fn register_fd(&mut self, _fd: RawFd, _token: usize) -> crate::Result<()> {
+        Err(fds_not_supported())
+    }
+
+    /// Returns a file descriptor which is readable when the source may have a new event.
+    ///
+    /// It's registered with the async runtime reactors by the native event streams.
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    fn raw_fd(&self) -> Option<RawFd> {
+        None
+    }
+
+    /// Returns the time left before the source has an event without any new input (like
+    /// the pending `Esc` key), `None` if there's no such event.
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    fn pending_leftover(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Deregisters the file descriptor registered with the `register_fd` method.
+    #[cfg(unix)]
+    fn deregister_fd(&mut self, _fd: RawFd) -> crate::Result<()> {
+        Err(fds_not_supported())
+    }
+
+    /// Handles the `SIGTSTP` signal and reports `Event::Suspend` & `Event::Resume`, `None`
+    /// stops handling it.
+    #[cfg(unix)]
+    fn set_suspend_handling(&mut self, _modes: Option<SuspendModes>) -> crate::Result<()> {
+        Err(io::Error::other("The event source doesn't support the suspend handling.").into())
+    }
+}
+
+#[cfg(unix)]
+fn fds_not_supported() -> crate::ErrorKind {
+    io::Error::other("The event source doesn't support file descriptors.").into()
 }
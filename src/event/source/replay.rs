@@ -0,0 +1,231 @@
+#[cfg(any(unix, feature = "event-stream"))]
+use std::io;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+#[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+use std::os::unix::io::{AsRawFd, RawFd};
+
+#[cfg(unix)]
+use mio::{Events, Poll};
+#[cfg(all(windows, feature = "event-stream"))]
+use winapi::{
+    shared::winerror::WAIT_TIMEOUT,
+    um::{
+        synchapi::WaitForSingleObject,
+        winbase::{INFINITE, WAIT_FAILED, WAIT_OBJECT_0},
+    },
+};
+
+use crate::Result;
+
+#[cfg(feature = "event-stream")]
+use super::super::sys::Waker;
+use super::super::{
+    recording::ReplayMode, source::EventSource, timeout::PollTimeout, Event, InternalEvent,
+};
+
+#[cfg(all(unix, feature = "event-stream"))]
+const WAKE_TOKEN: mio::Token = mio::Token(0);
+
+/// An event source replaying recorded events.
+///
+/// Once all events are replayed it behaves like a terminal without any input.
+pub(crate) struct ReplayEventSource {
+    events: VecDeque<(Duration, Event)>,
+    mode: ReplayMode,
+    // When was the last event replayed (or the replay started)
+    last: Instant,
+    #[cfg(unix)]
+    poll: Poll,
+    #[cfg(unix)]
+    poll_events: Events,
+    #[cfg(feature = "event-stream")]
+    waker: Waker,
+}
+
+impl ReplayEventSource {
+    pub(crate) fn new(events: Vec<(Duration, Event)>, mode: ReplayMode) -> Result<Self> {
+        #[cfg(unix)]
+        let poll = Poll::new()?;
+
+        #[cfg(all(unix, feature = "event-stream"))]
+        let waker = Waker::new(poll.registry(), WAKE_TOKEN)?;
+        #[cfg(all(windows, feature = "event-stream"))]
+        let waker = Waker::new()?;
+
+        Ok(ReplayEventSource {
+            events: events.into(),
+            mode,
+            last: Instant::now(),
+            #[cfg(unix)]
+            poll,
+            #[cfg(unix)]
+            poll_events: Events::with_capacity(1),
+            #[cfg(feature = "event-stream")]
+            waker,
+        })
+    }
+
+    /// Returns how long to wait for the next event, `None` if there are no more events.
+    fn next_event_leftover(&self) -> Option<Duration> {
+        let (delay, _) = self.events.front()?;
+
+        match self.mode {
+            ReplayMode::RealTime => Some(
+                delay
+                    .checked_sub(self.last.elapsed())
+                    .unwrap_or_else(|| Duration::from_secs(0)),
+            ),
+            ReplayMode::AsFastAsPossible => Some(Duration::from_secs(0)),
+        }
+    }
+
+    /// Sleeps for the given duration (forever if `None`) or until woken up by the `Waker`.
+    #[cfg(unix)]
+    fn sleep(&mut self, timeout: Option<Duration>) -> Result<()> {
+        match self.poll.poll(&mut self.poll_events, timeout) {
+            Ok(()) if !self.poll_events.is_empty() => Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "Poll operation was woken up by `Waker::wake`",
+            )
+            .into()),
+            // Signal interrupted the poll, the caller just retries
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => Ok(()),
+            result => Ok(result?),
+        }
+    }
+
+    #[cfg(all(windows, feature = "event-stream"))]
+    fn sleep(&mut self, timeout: Option<Duration>) -> Result<()> {
+        let dw_millis = timeout.map_or(INFINITE, |duration| duration.as_millis() as u32);
+        let semaphore = self.waker.semaphore();
+
+        match unsafe { WaitForSingleObject(**semaphore.handle(), dw_millis) } {
+            WAIT_OBJECT_0 => {
+                let _ = self.waker.reset();
+                Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "Poll operation was woken up by `Waker::wake`",
+                )
+                .into())
+            }
+            WAIT_TIMEOUT => Ok(()),
+            WAIT_FAILED => Err(io::Error::last_os_error().into()),
+            _ => Err(io::Error::other("WaitForSingleObject returned unexpected result.").into()),
+        }
+    }
+
+    #[cfg(all(windows, not(feature = "event-stream")))]
+    fn sleep(&mut self, timeout: Option<Duration>) -> Result<()> {
+        match timeout {
+            Some(timeout) => std::thread::sleep(timeout),
+            None => loop {
+                std::thread::park();
+            },
+        }
+        Ok(())
+    }
+}
+
+impl EventSource for ReplayEventSource {
+    fn try_read(&mut self, timeout: Option<Duration>) -> Result<Option<InternalEvent>> {
+        let poll_timeout = PollTimeout::new(timeout);
+
+        loop {
+            let leftover = self.next_event_leftover();
+
+            if leftover == Some(Duration::from_secs(0)) {
+                self.last = Instant::now();
+                return Ok(self
+                    .events
+                    .pop_front()
+                    .map(|(_, event)| InternalEvent::Event(event)));
+            }
+
+            if poll_timeout.elapsed() {
+                return Ok(None);
+            }
+
+            let sleep = match (leftover, poll_timeout.leftover()) {
+                (Some(leftover), Some(timeout)) => Some(leftover.min(timeout)),
+                (leftover, timeout) => leftover.or(timeout),
+            };
+            self.sleep(sleep)?;
+        }
+    }
+
+    #[cfg(feature = "event-stream")]
+    fn waker(&self) -> Waker {
+        self.waker.clone()
+    }
+
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.poll.as_raw_fd())
+    }
+
+    #[cfg(all(unix, any(feature = "tokio", feature = "async-io")))]
+    fn pending_leftover(&self) -> Option<Duration> {
+        self.next_event_leftover()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{
+        super::super::{recording::ReplayMode, source::EventSource, Event, InternalEvent},
+        ReplayEventSource,
+    };
+
+    fn events() -> Vec<(Duration, Event)> {
+        vec![
+            (Duration::from_millis(50), Event::Resize(10, 10)),
+            (Duration::from_millis(50), Event::FocusLost),
+        ]
+    }
+
+    #[test]
+    fn test_replay_as_fast_as_possible() {
+        let mut source = ReplayEventSource::new(events(), ReplayMode::AsFastAsPossible).unwrap();
+
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(0))).unwrap(),
+            Some(InternalEvent::Event(Event::Resize(10, 10)))
+        );
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(0))).unwrap(),
+            Some(InternalEvent::Event(Event::FocusLost))
+        );
+        assert_eq!(source.try_read(Some(Duration::from_secs(0))).unwrap(), None);
+    }
+
+    #[test]
+    fn test_replay_in_real_time() {
+        let start = Instant::now();
+        let mut source = ReplayEventSource::new(events(), ReplayMode::RealTime).unwrap();
+
+        assert_eq!(
+            source.try_read(Some(Duration::from_millis(10))).unwrap(),
+            None
+        );
+        assert_eq!(
+            source.try_read(None).unwrap(),
+            Some(InternalEvent::Event(Event::Resize(10, 10)))
+        );
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(
+            source.try_read(None).unwrap(),
+            Some(InternalEvent::Event(Event::FocusLost))
+        );
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(
+            source.try_read(Some(Duration::from_millis(10))).unwrap(),
+            None
+        );
+    }
+}
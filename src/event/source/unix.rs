@@ -1,15 +1,29 @@
 use mio::{unix::SourceFd, Events, Interest, Poll, Token};
 use signal_hook::iterator::Signals;
-use std::{collections::VecDeque, io, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::File,
+    io::{self, Read},
+    os::unix::io::{AsRawFd, FromRawFd, RawFd},
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::{ErrorKind, Result};
+use crate::{
+    terminal::{
+        sys::{suspend_count, SuspendHandler},
+        SuspendModes,
+    },
+    ErrorKind, Result,
+};
 
 #[cfg(feature = "event-stream")]
 use super::super::sys::Waker;
 use super::super::{
+    query::{forward_response, is_query_pending},
     source::EventSource,
     sys::unix::{
-        file_descriptor::{tty_fd, FileDesc},
+        file_descriptor::{pipe, tty_fd, FileDesc},
         parse::parse_event,
     },
     timeout::PollTimeout,
@@ -21,6 +35,8 @@ const TTY_TOKEN: Token = Token(0);
 const SIGNAL_TOKEN: Token = Token(1);
 #[cfg(feature = "event-stream")]
 const WAKE_TOKEN: Token = Token(2);
+// Registered file descriptors use the tokens starting at this one (token = first + fd)
+const FIRST_FD_TOKEN: usize = 3;
 
 // I (@zrzka) wasn't able to read more than 1_022 bytes when testing
 // reading on macOS/Linux -> we don't need bigger buffer and 1k of bytes
@@ -33,17 +49,101 @@ pub(crate) struct UnixInternalEventSource {
     parser: Parser,
     tty_buffer: [u8; TTY_BUFFER_SIZE],
     tty_fd: FileDesc,
-    signals: Signals,
+    // Keeps the owner of a borrowed `tty_fd` alive
+    _tty_fd_owner: Option<Box<dyn Send + Sync>>,
+    // The input was closed, `Event::Hangup` is reported once all read events are consumed
+    input_closed: bool,
+    signals: Option<Signals>,
+    // Signals reported as `Event::Signal`
+    reported_signals: Vec<libc::c_int>,
+    // Handles `SIGTSTP` and makes `SIGCONT` reported as `Event::Resume`
+    suspend_handler: Option<SuspendHandler>,
+    // The suspend count when the last `Event::Suspend` was reported
+    suspend_count: usize,
+    // Registered file descriptors and their tokens reported in `Event::Ready`
+    fds: HashMap<RawFd, usize>,
     #[cfg(feature = "event-stream")]
     waker: Waker,
 }
 
 impl UnixInternalEventSource {
+    /// Constructs the source of the global reader, which reads the responses of the queries.
     pub fn new() -> Result<Self> {
-        Ok(UnixInternalEventSource::from_file_descriptor(tty_fd()?)?)
+        let mut source = UnixInternalEventSource::from_file_descriptor(tty_fd()?, true, &[])?;
+        source.parser.query_responses = true;
+        Ok(source)
+    }
+
+    /// Constructs a new source reading from the terminal, which reports the given signals
+    /// as `Event::Signal` besides the terminal signals.
+    ///
+    /// The responses of the queries read by this source are handed to the global reader.
+    pub(crate) fn with_signals(signals: &[libc::c_int]) -> Result<Self> {
+        let mut source = UnixInternalEventSource::from_file_descriptor(tty_fd()?, true, signals)?;
+        source.parser.query_responses = true;
+        source.parser.forward_responses = true;
+        Ok(source)
     }
 
-    pub(crate) fn from_file_descriptor(input_fd: FileDesc) -> Result<Self> {
+    /// Constructs a new source reading from the file descriptor of the given owner.
+    ///
+    /// The owner is kept alive as long as the source, resize events are not reported.
+    pub(crate) fn from_fd_owner<T>(owner: T) -> Result<Self>
+    where
+        T: AsRawFd + Send + Sync + 'static,
+    {
+        let mut source = UnixInternalEventSource::from_file_descriptor(
+            FileDesc::new(owner.as_raw_fd(), false),
+            false,
+            &[],
+        )?;
+        source._tty_fd_owner = Some(Box::new(owner));
+        Ok(source)
+    }
+
+    /// Constructs a new source reading from the given reader.
+    ///
+    /// Bytes are copied from the reader to a pipe by a helper thread. Dropping the source
+    /// doesn't stop it, the thread exits once the reader reaches the end or fails, or once
+    /// its next write to the closed pipe fails. A reader blocked in `read` is leaked with
+    /// the thread until it returns more input or the end. Resize events are not reported.
+    pub(crate) fn from_reader<R>(mut reader: R) -> Result<Self>
+    where
+        R: Read + Send + 'static,
+    {
+        let (read_fd, write_fd) = pipe()?;
+
+        let mut writer = unsafe { File::from_raw_fd(write_fd) };
+        thread::spawn(move || {
+            let _ = io::copy(&mut reader, &mut writer);
+        });
+
+        UnixInternalEventSource::from_file_descriptor(read_fd, false, &[])
+    }
+
+    /// Constructs a new source reading from the given file descriptor.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_fd` - file descriptor to read the input from
+    /// * `terminal_signals` - specify if the `SIGWINCH` signal should be reported as `Event::Resize`
+    /// * `signals` - other signals to report as `Event::Signal`
+    pub(crate) fn from_file_descriptor(
+        input_fd: FileDesc,
+        terminal_signals: bool,
+        signals: &[libc::c_int],
+    ) -> Result<Self> {
+        if let Some(signal) = signals
+            .iter()
+            .find(|signal| signal_hook::FORBIDDEN.contains(signal))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("The signal {} can't be handled.", signal),
+            )
+            .into());
+        }
+
         let poll = Poll::new()?;
         let registry = poll.registry();
 
@@ -51,8 +151,19 @@ impl UnixInternalEventSource {
         let mut tty_ev = SourceFd(&tty_raw_fd);
         registry.register(&mut tty_ev, TTY_TOKEN, Interest::READABLE)?;
 
-        let mut signals = Signals::new(&[signal_hook::SIGWINCH])?;
-        registry.register(&mut signals, SIGNAL_TOKEN, Interest::READABLE)?;
+        let reported_signals = signals.to_vec();
+        let mut registered_signals = signals.to_vec();
+        if terminal_signals {
+            registered_signals.push(signal_hook::SIGWINCH);
+        }
+
+        let signals = if registered_signals.is_empty() {
+            None
+        } else {
+            let mut signals = Signals::new(&registered_signals)?;
+            registry.register(&mut signals, SIGNAL_TOKEN, Interest::READABLE)?;
+            Some(signals)
+        };
 
         #[cfg(feature = "event-stream")]
         let waker = Waker::new(registry, WAKE_TOKEN)?;
@@ -63,11 +174,31 @@ impl UnixInternalEventSource {
             parser: Parser::default(),
             tty_buffer: [0u8; TTY_BUFFER_SIZE],
             tty_fd: input_fd,
+            _tty_fd_owner: None,
+            input_closed: false,
             signals,
+            reported_signals,
+            suspend_handler: None,
+            suspend_count: 0,
+            fds: HashMap::new(),
             #[cfg(feature = "event-stream")]
             waker,
         })
     }
+
+    /// Stops reading the closed input and returns the `Event::Hangup` event.
+    ///
+    /// The source behaves like a terminal without any input afterwards.
+    fn hang_up(&mut self) -> Result<InternalEvent> {
+        let tty_raw_fd = self.tty_fd.raw_fd();
+        self.poll
+            .registry()
+            .deregister(&mut SourceFd(&tty_raw_fd))?;
+        self.parser.buffer.clear();
+        self.parser.escape_deadline = None;
+        self.input_closed = false;
+        Ok(InternalEvent::Event(Event::Hangup))
+    }
 }
 
 impl EventSource for UnixInternalEventSource {
@@ -79,7 +210,18 @@ impl EventSource for UnixInternalEventSource {
         let timeout = PollTimeout::new(timeout);
 
         loop {
-            if let Err(e) = self.poll.poll(&mut self.events, timeout.leftover()) {
+            if self.input_closed {
+                return self.hang_up().map(Some);
+            }
+
+            // Wake up in time to report the pending `Esc` key
+            let poll_timeout = match (timeout.leftover(), self.parser.escape_leftover()) {
+                (Some(leftover), Some(escape)) => Some(leftover.min(escape)),
+                (None, escape) => escape,
+                (leftover, None) => leftover,
+            };
+
+            if let Err(e) = self.poll.poll(&mut self.events, poll_timeout) {
                 // Mio will throw an interrupted error in case of cursor position retrieval. We need to retry until it succeeds.
                 // Previous versions of Mio (< 0.7) would automatically retry the poll call if it was interrupted (if EINTR was returned).
                 // https://docs.rs/mio/0.7.0/mio/struct.Poll.html#notes
@@ -91,21 +233,60 @@ impl EventSource for UnixInternalEventSource {
             };
 
             if self.events.is_empty() {
-                // No readiness events = timeout
-                return Ok(None);
+                if let Some(event) = self.parser.expire_escape() {
+                    return Ok(Some(event));
+                }
+
+                if self.parser.escape_leftover().is_none() || timeout.elapsed() {
+                    // No readiness events = timeout
+                    return Ok(None);
+                }
+
+                continue;
+            }
+
+            // The readiness of the registered file descriptors is queued first, the terminal
+            // input may return from the loop below and it wouldn't be reported again
+            for event in self.events.iter() {
+                if let Some(fd) = event.token().0.checked_sub(FIRST_FD_TOKEN) {
+                    if let Some(&token) = self.fds.get(&(fd as RawFd)) {
+                        self.parser
+                            .internal_events
+                            .push_back(InternalEvent::Event(Event::Ready(token)));
+                    }
+                }
             }
 
-            for token in self.events.iter().map(|x| x.token()) {
-                match token {
+            for event in self.events.iter() {
+                match event.token() {
                     TTY_TOKEN => {
                         loop {
                             match self.tty_fd.read(&mut self.tty_buffer, TTY_BUFFER_SIZE) {
+                                // End of file, the other side of the pipe, socket, ... was closed
+                                Ok(0) => {
+                                    self.input_closed = true;
+                                    break;
+                                }
                                 Ok(read_count) => {
-                                    if read_count > 0 {
-                                        self.parser.advance(
-                                            &self.tty_buffer[..read_count],
-                                            read_count == TTY_BUFFER_SIZE,
-                                        );
+                                    self.parser.advance(
+                                        &self.tty_buffer[..read_count],
+                                        read_count == TTY_BUFFER_SIZE,
+                                    );
+
+                                    // Everything was read and the input was closed, the end
+                                    // of file wouldn't be signalled by another readiness event
+                                    if read_count < TTY_BUFFER_SIZE && event.is_read_closed() {
+                                        self.input_closed = true;
+                                    }
+
+                                    if let Some(event) = self.parser.next() {
+                                        return Ok(Some(event));
+                                    }
+
+                                    // Everything available was read, don't block in the next read
+                                    // and wait for another readiness event (or escape timeout)
+                                    if read_count < TTY_BUFFER_SIZE {
+                                        break;
                                     }
                                 }
                                 Err(ErrorKind::IoError(e)) => {
@@ -117,6 +298,13 @@ impl EventSource for UnixInternalEventSource {
                                     else if e.kind() == io::ErrorKind::Interrupted {
                                         continue;
                                     }
+                                    // The terminal was closed (SSH disconnect, closed window, ...)
+                                    else if e.raw_os_error() == Some(libc::EIO) {
+                                        self.input_closed = true;
+                                        break;
+                                    } else {
+                                        return Err(ErrorKind::IoError(e));
+                                    }
                                 }
                                 Err(e) => return Err(e),
                             };
@@ -127,8 +315,15 @@ impl EventSource for UnixInternalEventSource {
                         }
                     }
                     SIGNAL_TOKEN => {
-                        for signal in &self.signals {
-                            match signal as libc::c_int {
+                        // All pending signals must be handled, the readiness event isn't
+                        // repeated for the rest of them
+                        let pending: Vec<_> = match &self.signals {
+                            Some(signals) => signals.pending().collect(),
+                            None => Vec::new(),
+                        };
+
+                        for signal in pending {
+                            let event = match signal as libc::c_int {
                                 signal_hook::SIGWINCH => {
                                     // TODO Should we remove tput?
                                     //
@@ -138,12 +333,32 @@ impl EventSource for UnixInternalEventSource {
                                     // it's a really long time from the mio, async-std/tokio executor, ...
                                     // point of view.
                                     let new_size = crate::terminal::size()?;
-                                    return Ok(Some(InternalEvent::Event(Event::Resize(
-                                        new_size.0, new_size.1,
-                                    ))));
+                                    Event::Resize(new_size.0, new_size.1)
+                                }
+                                libc::SIGCONT if self.suspend_handler.is_some() => {
+                                    // Waits for the terminal to be reapplied
+                                    let count = suspend_count();
+                                    if count != self.suspend_count {
+                                        self.suspend_count = count;
+                                        self.parser
+                                            .internal_events
+                                            .push_back(InternalEvent::Event(Event::Suspend));
+                                    }
+                                    Event::Resume
+                                }
+                                signal if self.reported_signals.contains(&signal) => {
+                                    Event::Signal(signal)
                                 }
-                                _ => unreachable!("Synchronize signal registration & handling"),
+                                // `SIGCONT` registered by the disabled suspend handling
+                                _ => continue,
                             };
+                            self.parser
+                                .internal_events
+                                .push_back(InternalEvent::Event(event));
+                        }
+
+                        if let Some(event) = self.parser.next() {
+                            return Ok(Some(event));
                         }
                     }
                     #[cfg(feature = "event-stream")]
@@ -154,10 +369,15 @@ impl EventSource for UnixInternalEventSource {
                         )
                         .into());
                     }
+                    Token(token) if token >= FIRST_FD_TOKEN => {}
                     _ => unreachable!("Synchronize Evented handle registration & token handling"),
                 }
             }
 
+            if let Some(event) = self.parser.next() {
+                return Ok(Some(event));
+            }
+
             // Processing above can take some time, check if timeout expired
             if timeout.elapsed() {
                 return Ok(None);
@@ -169,6 +389,81 @@ impl EventSource for UnixInternalEventSource {
     fn waker(&self) -> Waker {
         self.waker.clone()
     }
+
+    fn set_escape_timeout(&mut self, timeout: Duration) {
+        self.parser.escape_timeout = timeout;
+    }
+
+    fn set_report_unknown_sequences(&mut self, report: bool) {
+        self.parser.report_unknown = report;
+    }
+
+    fn set_pixel_mouse_reports(&mut self, pixels: bool) {
+        self.parser.pixel_mouse = pixels;
+    }
+
+    #[cfg(any(feature = "tokio", feature = "async-io"))]
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.poll.as_raw_fd())
+    }
+
+    #[cfg(any(feature = "tokio", feature = "async-io"))]
+    fn pending_leftover(&self) -> Option<Duration> {
+        self.parser.escape_leftover()
+    }
+
+    fn set_suspend_handling(&mut self, modes: Option<SuspendModes>) -> Result<()> {
+        let modes = match modes {
+            Some(modes) => modes,
+            None => {
+                self.suspend_handler = None;
+                return Ok(());
+            }
+        };
+
+        if let Some(handler) = &self.suspend_handler {
+            handler.set_modes(modes);
+            return Ok(());
+        }
+
+        match &self.signals {
+            Some(signals) => signals.add_signal(libc::SIGCONT)?,
+            None => {
+                let mut signals = Signals::new([libc::SIGCONT])?;
+                self.poll
+                    .registry()
+                    .register(&mut signals, SIGNAL_TOKEN, Interest::READABLE)?;
+                self.signals = Some(signals);
+            }
+        }
+
+        self.suspend_count = suspend_count();
+        self.suspend_handler = Some(SuspendHandler::new(modes)?);
+        Ok(())
+    }
+
+    fn register_fd(&mut self, fd: RawFd, token: usize) -> Result<()> {
+        self.poll.registry().register(
+            &mut SourceFd(&fd),
+            Token(FIRST_FD_TOKEN + fd as usize),
+            Interest::READABLE,
+        )?;
+        self.fds.insert(fd, token);
+        Ok(())
+    }
+
+    fn deregister_fd(&mut self, fd: RawFd) -> Result<()> {
+        if self.fds.remove(&fd).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "The file descriptor is not registered.",
+            )
+            .into());
+        }
+
+        self.poll.registry().deregister(&mut SourceFd(&fd))?;
+        Ok(())
+    }
 }
 
 //
@@ -181,6 +476,20 @@ impl EventSource for UnixInternalEventSource {
 struct Parser {
     buffer: Vec<u8>,
     internal_events: VecDeque<InternalEvent>,
+    // How long to wait for the rest of an escape sequence before a lone `ESC`
+    // byte is reported as the `Esc` key. Zero reports it immediately once no
+    // more input is available.
+    escape_timeout: Duration,
+    // When the buffered lone `ESC` byte should be reported as the `Esc` key
+    escape_deadline: Option<Instant>,
+    // Report sequences which can't be parsed as `Event::Unknown`
+    report_unknown: bool,
+    // Report the SGR mouse reports as `Event::PixelMouse`
+    pixel_mouse: bool,
+    // The input is read from the terminal, the responses of the pending queries are expected
+    query_responses: bool,
+    // Hand the responses to the global reader instead of keeping them in `internal_events`
+    forward_responses: bool,
 }
 
 impl Default for Parser {
@@ -192,6 +501,9 @@ impl Default for Parser {
             //
             // Probably not worth spending more time on this as "there's a plan"
             // to use the anes crate parser.
+            //
+            // Bracketed paste is the exception, the whole pasted text is collected
+            // here before it's reported as a single event.
             buffer: Vec::with_capacity(256),
             // TTY_BUFFER_SIZE is 1_024 bytes. How many ANSI escape sequences can
             // fit? What is an average sequence length? Let's guess here
@@ -203,20 +515,36 @@ impl Default for Parser {
             // method implementation, all events are consumed before the next TTY_BUFFER
             // is processed -> events pushed.
             internal_events: VecDeque::with_capacity(128),
+            escape_timeout: Duration::from_secs(0),
+            escape_deadline: None,
+            report_unknown: false,
+            pixel_mouse: false,
+            query_responses: false,
+            forward_responses: false,
         }
     }
 }
 
 impl Parser {
     fn advance(&mut self, buffer: &[u8], more: bool) {
+        let wait_for_escape = self.escape_timeout > Duration::from_secs(0);
+
         for (idx, byte) in buffer.iter().enumerate() {
-            let more = idx + 1 < buffer.len() || more;
+            let more = idx + 1 < buffer.len() || more || wait_for_escape;
 
             self.buffer.push(*byte);
 
-            match parse_event(&self.buffer, more) {
+            match parse_event(&self.buffer, more, self.string_sequences()) {
+                // SGR-Pixels uses the SGR mouse encoding
+                Ok(Some(InternalEvent::Event(Event::Mouse(event))))
+                    if self.pixel_mouse && self.buffer.starts_with(b"\x1B[<") =>
+                {
+                    self.internal_events
+                        .push_back(InternalEvent::Event(Event::PixelMouse(event)));
+                    self.buffer.clear();
+                }
                 Ok(Some(ie)) => {
-                    self.internal_events.push_back(ie);
+                    self.push_event(ie);
                     self.buffer.clear();
                 }
                 Ok(None) => {
@@ -226,10 +554,68 @@ impl Parser {
                 Err(_) => {
                     // Event can't be parsed (not enough parameters, parameter is not a number, ...).
                     // Clear the buffer and continue with another sequence.
+                    if self.report_unknown {
+                        self.internal_events
+                            .push_back(InternalEvent::Event(Event::Unknown(self.buffer.clone())));
+                    }
                     self.buffer.clear();
                 }
             }
         }
+
+        self.escape_deadline =
+            if wait_for_escape && is_pending_escape(&self.buffer, self.string_sequences()) {
+                Some(Instant::now() + self.escape_timeout)
+            } else {
+                None
+            };
+    }
+
+    /// Returns whether the string sequences (OSC, DCS, ...) are expected in the input.
+    ///
+    /// They're collected until the BEL or ST terminator, the `ESC ]`, `ESC P`, ... bytes
+    /// followed by more input are the `Alt` key combinations otherwise.
+    fn string_sequences(&self) -> bool {
+        self.report_unknown || (self.query_responses && is_query_pending())
+    }
+
+    fn push_event(&mut self, event: InternalEvent) {
+        match event {
+            InternalEvent::Event(_) => self.internal_events.push_back(event),
+            response if self.forward_responses => forward_response(response),
+            response => self.internal_events.push_back(response),
+        }
+    }
+
+    /// Returns the time left before the pending `Esc` key is reported, if there's any.
+    fn escape_leftover(&self) -> Option<Duration> {
+        self.escape_deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Reports the pending `Esc` key (or `Alt` key combination) if the escape timeout elapsed.
+    fn expire_escape(&mut self) -> Option<InternalEvent> {
+        match self.escape_deadline {
+            Some(deadline) if deadline <= Instant::now() => {
+                self.escape_deadline = None;
+                let event = parse_event(&self.buffer, false, self.string_sequences())
+                    .ok()
+                    .flatten();
+                self.buffer.clear();
+                event
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns whether the buffer is a lone `ESC` byte or an `ESC` followed by a string
+/// sequence introducer, which are reported as keys if no more input arrives in time.
+fn is_pending_escape(buffer: &[u8], string_sequences: bool) -> bool {
+    match buffer {
+        [b'\x1B'] => true,
+        [b'\x1B', introducer] => string_sequences && b"]P_^X".contains(introducer),
+        _ => false,
     }
 }
 
@@ -240,3 +626,173 @@ impl Iterator for Parser {
         self.internal_events.pop_front()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        super::super::{
+            filter::PrimaryDeviceAttributesFilter,
+            query::take_forwarded_response,
+            source::EventSource,
+            sys::unix::file_descriptor::{pipe, FileDesc},
+            Event, InternalEvent, KeyCode,
+        },
+        SuspendModes, UnixInternalEventSource,
+    };
+
+    fn sigtstp_handler() -> libc::sighandler_t {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            assert_eq!(
+                libc::sigaction(libc::SIGTSTP, std::ptr::null(), &mut action),
+                0
+            );
+            action.sa_sigaction
+        }
+    }
+
+    #[test]
+    fn test_pipe_is_closed_on_exec() {
+        let (read_fd, write_fd) = pipe().unwrap();
+        let write_fd = FileDesc::new(write_fd, true);
+
+        for fd in [read_fd.raw_fd(), write_fd.raw_fd()].iter() {
+            let flags = unsafe { libc::fcntl(*fd, libc::F_GETFD) };
+            assert_eq!(flags & libc::FD_CLOEXEC, libc::FD_CLOEXEC);
+        }
+    }
+
+    #[test]
+    fn test_suspend_handling() {
+        let (read_fd, write_fd) = pipe().unwrap();
+        let _write_fd = FileDesc::new(write_fd, true);
+        let mut source = UnixInternalEventSource::from_file_descriptor(read_fd, true, &[]).unwrap();
+
+        // Not handled by default
+        assert_eq!(sigtstp_handler(), libc::SIG_DFL);
+        assert_eq!(unsafe { libc::raise(libc::SIGCONT) }, 0);
+        assert_eq!(
+            source.try_read(Some(Duration::from_millis(10))).unwrap(),
+            None
+        );
+
+        source
+            .set_suspend_handling(Some(SuspendModes::default()))
+            .unwrap();
+        assert_ne!(sigtstp_handler(), libc::SIG_DFL);
+
+        // Continued without being suspended
+        assert_eq!(unsafe { libc::raise(libc::SIGCONT) }, 0);
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(1))).unwrap(),
+            Some(InternalEvent::Event(Event::Resume))
+        );
+
+        source.set_suspend_handling(None).unwrap();
+        assert_eq!(sigtstp_handler(), libc::SIG_DFL);
+    }
+
+    #[test]
+    fn test_report_signals() {
+        let (read_fd, write_fd) = pipe().unwrap();
+        let _write_fd = FileDesc::new(write_fd, true);
+        let mut source =
+            UnixInternalEventSource::from_file_descriptor(read_fd, false, &[libc::SIGUSR1])
+                .unwrap();
+
+        assert_eq!(unsafe { libc::raise(libc::SIGUSR1) }, 0);
+
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(1))).unwrap(),
+            Some(InternalEvent::Event(Event::Signal(libc::SIGUSR1)))
+        );
+    }
+
+    #[test]
+    fn test_forbidden_signals() {
+        let (read_fd, write_fd) = pipe().unwrap();
+        let _write_fd = FileDesc::new(write_fd, true);
+
+        assert!(
+            UnixInternalEventSource::from_file_descriptor(read_fd, false, &[libc::SIGKILL])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_forward_responses() {
+        let (read_fd, write_fd) = pipe().unwrap();
+        let write_fd = FileDesc::new(write_fd, true);
+        let mut source =
+            UnixInternalEventSource::from_file_descriptor(read_fd, false, &[]).unwrap();
+        source.parser.forward_responses = true;
+
+        let input = b"\x1B[?62;4ca";
+        assert_eq!(
+            unsafe { libc::write(write_fd.raw_fd(), input.as_ptr().cast(), input.len()) },
+            input.len() as isize
+        );
+
+        // The response is handed over, the key is reported
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(1))).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Char('a').into())))
+        );
+        assert!(matches!(
+            take_forwarded_response(&PrimaryDeviceAttributesFilter),
+            Some(InternalEvent::PrimaryDeviceAttributes(_))
+        ));
+    }
+
+    #[test]
+    fn test_hangup() {
+        let (read_fd, write_fd) = pipe().unwrap();
+        let write_fd = FileDesc::new(write_fd, true);
+        let mut source =
+            UnixInternalEventSource::from_file_descriptor(read_fd, false, &[]).unwrap();
+
+        assert_eq!(
+            source.try_read(Some(Duration::from_millis(10))).unwrap(),
+            None
+        );
+
+        drop(write_fd);
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(1))).unwrap(),
+            Some(InternalEvent::Event(Event::Hangup))
+        );
+        assert_eq!(
+            source.try_read(Some(Duration::from_millis(10))).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_registered_fds() {
+        let (tty_fd, tty_write_fd) = pipe().unwrap();
+        let _tty_write_fd = FileDesc::new(tty_write_fd, true);
+        let (read_fd, write_fd) = pipe().unwrap();
+        let write_fd = FileDesc::new(write_fd, true);
+        let mut source = UnixInternalEventSource::from_file_descriptor(tty_fd, false, &[]).unwrap();
+
+        source.register_fd(read_fd.raw_fd(), 42).unwrap();
+        assert_eq!(
+            source.try_read(Some(Duration::from_millis(10))).unwrap(),
+            None
+        );
+
+        assert_eq!(
+            unsafe { libc::write(write_fd.raw_fd(), b"x".as_ptr().cast(), 1) },
+            1
+        );
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(1))).unwrap(),
+            Some(InternalEvent::Event(Event::Ready(42)))
+        );
+
+        source.deregister_fd(read_fd.raw_fd()).unwrap();
+        assert!(source.deregister_fd(read_fd.raw_fd()).is_err());
+    }
+}
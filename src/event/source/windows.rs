@@ -41,6 +41,13 @@ impl EventSource for WindowsEventSource {
                         InputRecord::WindowBufferSizeEvent(record) => {
                             Some(Event::Resize(record.size.x as u16, record.size.y as u16))
                         }
+                        InputRecord::FocusEvent(record) => {
+                            if record.set_focus {
+                                Some(Event::FocusGained)
+                            } else {
+                                Some(Event::FocusLost)
+                            }
+                        }
                         _ => None,
                     };
 
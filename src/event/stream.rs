@@ -16,10 +16,7 @@ use futures_core::{
 
 use crate::Result;
 
-use super::{
-    filter::EventFilter, poll_internal, read_internal, sys::Waker, Event, InternalEvent,
-    INTERNAL_EVENT_READER,
-};
+use super::{filter::EventFilter, sys::Waker, Event, EventReader, InternalEvent, EVENT_READER};
 
 /// A stream of `Result<Event>`.
 ///
@@ -32,8 +29,13 @@ use super::{
 ///
 /// Check the [examples](https://github.com/crossterm-rs/crossterm/tree/master/examples) folder to see how to use
 /// it (`event-stream-*`).
+///
+/// The stream reads events with the same reader as the [`poll`](fn.poll.html) &
+/// [`read`](fn.read.html) functions, use [`EventReader::stream`](struct.EventReader.html#method.stream)
+/// to get a stream of another reader.
 #[derive(Debug)]
 pub struct EventStream {
+    reader: EventReader,
     poll_internal_waker: Waker,
     stream_wake_task_executed: Arc<AtomicBool>,
     stream_wake_task_should_shutdown: Arc<AtomicBool>,
@@ -42,12 +44,25 @@ pub struct EventStream {
 
 impl Default for EventStream {
     fn default() -> Self {
+        EventStream::with_reader(EVENT_READER.clone())
+    }
+}
+
+impl EventStream {
+    /// Constructs a new instance of `EventStream`.
+    pub fn new() -> EventStream {
+        EventStream::default()
+    }
+
+    /// Constructs a new instance of `EventStream` reading events with the given reader.
+    pub(crate) fn with_reader(reader: EventReader) -> EventStream {
         let (task_sender, receiver) = mpsc::sync_channel::<Task>(1);
 
+        let thread_reader = reader.clone();
         thread::spawn(move || {
             while let Ok(task) = receiver.recv() {
                 loop {
-                    if let Ok(true) = poll_internal(None, &EventFilter) {
+                    if let Ok(true) = thread_reader.poll_internal(None, &EventFilter) {
                         break;
                     }
 
@@ -62,7 +77,8 @@ impl Default for EventStream {
         });
 
         EventStream {
-            poll_internal_waker: INTERNAL_EVENT_READER.write().waker(),
+            poll_internal_waker: reader.waker(),
+            reader,
             stream_wake_task_executed: Arc::new(AtomicBool::new(false)),
             stream_wake_task_should_shutdown: Arc::new(AtomicBool::new(false)),
             task_sender,
@@ -70,13 +86,6 @@ impl Default for EventStream {
     }
 }
 
-impl EventStream {
-    /// Constructs a new instance of `EventStream`.
-    pub fn new() -> EventStream {
-        EventStream::default()
-    }
-}
-
 struct Task {
     stream_waker: std::task::Waker,
     stream_wake_task_executed: Arc<AtomicBool>,
@@ -105,8 +114,11 @@ impl Stream for EventStream {
     type Item = Result<Event>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let result = match poll_internal(Some(Duration::from_secs(0)), &EventFilter) {
-            Ok(true) => match read_internal(&EventFilter) {
+        let result = match self
+            .reader
+            .poll_internal(Some(Duration::from_secs(0)), &EventFilter)
+        {
+            Ok(true) => match self.reader.read_internal(&EventFilter) {
                 Ok(InternalEvent::Event(event)) => Poll::Ready(Some(Ok(event))),
                 Err(e) => Poll::Ready(Some(Err(e))),
                 #[cfg(unix)]
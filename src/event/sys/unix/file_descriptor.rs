@@ -80,3 +80,32 @@ pub fn tty_fd() -> Result<FileDesc> {
 
     Ok(FileDesc::new(fd, close_on_drop))
 }
+
+/// Creates a pipe, returns the non-blocking read end and the raw write end.
+///
+/// Both ends are closed on exec, they don't leak into child processes. The write end must
+/// be closed by the caller.
+pub(crate) fn pipe() -> Result<(FileDesc, RawFd)> {
+    let mut fds = [0 as RawFd; 2];
+
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(ErrorKind::IoError(io::Error::last_os_error()));
+    }
+
+    let read_fd = FileDesc::new(fds[0], true);
+
+    unsafe {
+        let flags = libc::fcntl(fds[0], libc::F_GETFL);
+        if flags < 0
+            || libc::fcntl(fds[0], libc::F_SETFL, flags | libc::O_NONBLOCK) < 0
+            || libc::fcntl(fds[0], libc::F_SETFD, libc::FD_CLOEXEC) < 0
+            || libc::fcntl(fds[1], libc::F_SETFD, libc::FD_CLOEXEC) < 0
+        {
+            let error = io::Error::last_os_error();
+            libc::close(fds[1]);
+            return Err(ErrorKind::IoError(error));
+        }
+    }
+
+    Ok((read_fd, fds[1]))
+}
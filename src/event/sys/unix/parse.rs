@@ -1,5 +1,10 @@
 use crate::{
-    event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent},
+    event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MediaKeyCode,
+        ModifierKeyCode, MouseButton, MouseEvent,
+    },
+    style::Color,
+    terminal::{ModeStatus, PrimaryDeviceAttributes, SecondaryDeviceAttributes, TerminalMode},
     ErrorKind, Result,
 };
 
@@ -25,7 +30,14 @@ fn could_not_parse_event_error() -> ErrorKind {
     ))
 }
 
-pub(crate) fn parse_event(buffer: &[u8], input_available: bool) -> Result<Option<InternalEvent>> {
+// `string_sequences` - string sequences (OSC, DCS, ...) are parsed only if the reader expects
+// them (a query waits for the response or unknown sequences are reported), `ESC ]`, `ESC P`,
+// ... are the `Alt` key combinations otherwise
+pub(crate) fn parse_event(
+    buffer: &[u8],
+    input_available: bool,
+    string_sequences: bool,
+) -> Result<Option<InternalEvent>> {
     if buffer.is_empty() {
         return Ok(None);
     }
@@ -45,16 +57,18 @@ pub(crate) fn parse_event(buffer: &[u8], input_available: bool) -> Result<Option
                         if buffer.len() == 2 {
                             Ok(None)
                         } else {
-                            match buffer[2] {
-                                // F1-F4
-                                val @ b'P'..=b'S' => Ok(Some(InternalEvent::Event(Event::Key(
-                                    KeyCode::F(1 + val - b'P').into(),
-                                )))),
-                                _ => Err(could_not_parse_event_error()),
-                            }
+                            parse_ss3_key_code(buffer[2])
+                                .map(|event| Some(InternalEvent::Event(Event::Key(event))))
+                                .ok_or_else(could_not_parse_event_error)
                         }
                     }
                     b'[' => parse_csi(buffer),
+                    // A lone `ESC ]`, `ESC P`, ... is the Alt key combination
+                    b']' | b'P' | b'_' | b'^' | b'X'
+                        if string_sequences && (buffer.len() > 2 || input_available) =>
+                    {
+                        parse_string_sequence(buffer)
+                    }
                     b'\x1B' => Ok(Some(InternalEvent::Event(Event::Key(KeyCode::Esc.into())))),
                     _ => parse_utf8_char(&buffer[1..]).map(|maybe_char| {
                         maybe_char
@@ -102,6 +116,119 @@ pub(crate) fn parse_event(buffer: &[u8], input_available: bool) -> Result<Option
     }
 }
 
+// OSC (ESC ]), DCS (ESC P), APC (ESC _), PM (ESC ^) and SOS (ESC X) sequences,
+// terminated by ST (ESC \\) or BEL
+pub(crate) fn parse_string_sequence(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    match (buffer[1], string_sequence_content(buffer)?) {
+        // XTVERSION response, DCS > | text ST
+        (b'P', Some(content)) if content.starts_with(b">|") => Ok(Some(
+            InternalEvent::TerminalVersion(String::from_utf8_lossy(&content[2..]).into_owned()),
+        )),
+        // Dynamic colors response, OSC Ps ; rgb:r/g/b ST
+        (b']', Some(content)) => parse_osc_color(content),
+        (_, Some(_)) => Err(could_not_parse_event_error()),
+        (_, None) => Ok(None),
+    }
+}
+
+// Parses the `10;rgb:rrrr/gggg/bbbb` content of the dynamic colors response
+fn parse_osc_color(content: &[u8]) -> Result<Option<InternalEvent>> {
+    let content = std::str::from_utf8(content).map_err(|_| could_not_parse_event_error())?;
+    let mut split = content.splitn(2, ';');
+
+    let code = next_parsed::<u16>(&mut split)?;
+    let value = split.next().ok_or_else(could_not_parse_event_error)?;
+
+    // Some terminals report the alpha channel as well
+    let components = if let Some(rgb) = value.strip_prefix("rgb:") {
+        rgb
+    } else if let Some(rgba) = value.strip_prefix("rgba:") {
+        rgba
+    } else {
+        return Err(could_not_parse_event_error());
+    };
+
+    let mut components = components.split('/').map(parse_color_component);
+    let r = components
+        .next()
+        .ok_or_else(could_not_parse_event_error)??;
+    let g = components
+        .next()
+        .ok_or_else(could_not_parse_event_error)??;
+    let b = components
+        .next()
+        .ok_or_else(could_not_parse_event_error)??;
+
+    Ok(Some(InternalEvent::TerminalColor(
+        code,
+        Color::Rgb { r, g, b },
+    )))
+}
+
+// Scales the 1-4 hex digits color component to 8 bits
+fn parse_color_component(component: &str) -> Result<u8> {
+    if component.is_empty() || component.len() > 4 {
+        return Err(could_not_parse_event_error());
+    }
+
+    let value = u32::from_str_radix(component, 16).map_err(|_| could_not_parse_event_error())?;
+    let max = (1u32 << (4 * component.len())) - 1;
+
+    Ok((value * 255 / max) as u8)
+}
+
+// Returns the content of a complete string sequence (without the introducer & terminator)
+fn string_sequence_content(buffer: &[u8]) -> Result<Option<&[u8]>> {
+    assert!(buffer.len() >= 2 && buffer[0] == b'\x1B');
+
+    let content = &buffer[2..];
+    match content.last() {
+        Some(b'\x07') => Ok(Some(&content[..content.len() - 1])),
+        Some(b'\\') if content.ends_with(b"\x1B\\") => Ok(Some(&content[..content.len() - 2])),
+        // ESC can only start the ST terminator
+        _ if content.len() > 1 && content[content.len() - 2] == b'\x1B' => {
+            Err(could_not_parse_event_error())
+        }
+        _ => Ok(None),
+    }
+}
+
+// SS3 (ESC O final) keys, sent in the application cursor & keypad modes
+fn parse_ss3_key_code(final_byte: u8) -> Option<KeyEvent> {
+    let keypad = |code| {
+        KeyEvent::new_with_kind_and_state(
+            code,
+            KeyModifiers::empty(),
+            KeyEventKind::Press,
+            KeyEventState::KEYPAD,
+        )
+    };
+
+    let event = match final_byte {
+        // F1-F4
+        b'P'..=b'S' => KeyCode::F(1 + final_byte - b'P').into(),
+        b'A' => KeyCode::Up.into(),
+        b'B' => KeyCode::Down.into(),
+        b'C' => KeyCode::Right.into(),
+        b'D' => KeyCode::Left.into(),
+        b'H' => KeyCode::Home.into(),
+        b'F' => KeyCode::End.into(),
+        b'E' => keypad(KeyCode::KeypadBegin),
+        b'M' => keypad(KeyCode::Enter),
+        b'X' => keypad(KeyCode::Char('=')),
+        b'j' => keypad(KeyCode::Char('*')),
+        b'k' => keypad(KeyCode::Char('+')),
+        b'l' => keypad(KeyCode::Char(',')),
+        b'm' => keypad(KeyCode::Char('-')),
+        b'n' => keypad(KeyCode::Char('.')),
+        b'o' => keypad(KeyCode::Char('/')),
+        b'p'..=b'y' => keypad(KeyCode::Char((b'0' + final_byte - b'p') as char)),
+        _ => return None,
+    };
+
+    Some(event)
+}
+
 // converts KeyCode to KeyEvent (adds shift modifier in case of uppercase characters)
 fn char_code_to_event(code: KeyCode) -> KeyEvent {
     let modifiers = match code {
@@ -137,12 +264,16 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> Result<Option<InternalEvent>> {
         b'B' => Some(Event::Key(KeyCode::Down.into())),
         b'H' => Some(Event::Key(KeyCode::Home.into())),
         b'F' => Some(Event::Key(KeyCode::End.into())),
-        b'Z' => Some(Event::Key(KeyEvent {
-            code: KeyCode::BackTab,
-            modifiers: KeyModifiers::SHIFT,
-        })),
+        b'E' => Some(Event::Key(KeyCode::KeypadBegin.into())),
+        b'Z' => Some(Event::Key(KeyEvent::new(
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT,
+        ))),
+        b'I' => Some(Event::FocusGained),
+        b'O' => Some(Event::FocusLost),
         b'M' => return parse_csi_x10_mouse(buffer),
         b'<' => return parse_csi_xterm_mouse(buffer),
+        b'?' | b'>' => return parse_csi_query_response(buffer),
         b'0'..=b'9' => {
             // Numbered escape code.
             if buffer.len() == 3 {
@@ -151,13 +282,24 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> Result<Option<InternalEvent>> {
                 // The final byte of a CSI sequence can be in the range 64-126, so
                 // let's keep reading anything else.
                 let last_byte = *buffer.last().unwrap();
-                if last_byte < 64 || last_byte > 126 {
+                if (last_byte < 64 || last_byte > 126)
+                    && (last_byte != b'$' || buffer.contains(&b';'))
+                {
                     None
+                } else if buffer.starts_with(b"\x1B[200~") {
+                    return parse_csi_bracketed_paste(buffer);
                 } else {
                     match buffer[buffer.len() - 1] {
                         b'M' => return parse_csi_rxvt_mouse(buffer),
-                        b'~' => return parse_csi_special_key_code(buffer),
+                        b'~' | b'^' | b'@' => return parse_csi_special_key_code(buffer),
+                        // `$` is an intermediate byte in other sequences (like `CSI 4;1 $ y`),
+                        // rxvt sends just the key number
+                        b'$' if !buffer.contains(&b';') => {
+                            return parse_csi_special_key_code(buffer)
+                        }
                         b'R' => return parse_csi_cursor_position(buffer),
+                        b'y' if buffer.ends_with(b"$y") => return parse_csi_mode_report(buffer),
+                        b'u' => return parse_csi_u_encoded_key_code(buffer),
                         _ => return parse_csi_modifier_key_code(buffer),
                     }
                 }
@@ -169,6 +311,102 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> Result<Option<InternalEvent>> {
     Ok(input_event.map(InternalEvent::Event))
 }
 
+// Responses to the terminal queries with the `?` or `>` private parameter prefix
+pub(crate) fn parse_csi_query_response(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    assert!(buffer.starts_with(b"\x1B[?") || buffer.starts_with(b"\x1B[>")); // ESC [ ? or ESC [ >
+
+    // The final byte of a CSI sequence is in the range 64-126
+    let last_byte = *buffer.last().unwrap();
+    if buffer.len() == 3 || !(64..=126).contains(&last_byte) {
+        return Ok(None);
+    }
+
+    match (buffer[2], last_byte) {
+        (b'?', b'c') => parse_csi_primary_device_attributes(buffer),
+        (b'>', b'c') => parse_csi_secondary_device_attributes(buffer),
+        (b'?', b'y') if buffer.ends_with(b"$y") => parse_csi_mode_report(buffer),
+        _ => Err(could_not_parse_event_error()),
+    }
+}
+
+// Parses the numeric parameters between the `skip` prefix bytes and the `suffix` bytes
+fn parse_csi_parameters(buffer: &[u8], skip: usize, suffix: usize) -> Result<Vec<u16>> {
+    let s = std::str::from_utf8(&buffer[skip..buffer.len() - suffix])
+        .map_err(|_| could_not_parse_event_error())?;
+
+    s.split(';')
+        .map(|parameter| {
+            parameter
+                .parse::<u16>()
+                .map_err(|_| could_not_parse_event_error())
+        })
+        .collect()
+}
+
+pub(crate) fn parse_csi_primary_device_attributes(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ ? Pclass ; Pfeature ; ... c
+    assert!(buffer.starts_with(b"\x1B[?")); // ESC [ ?
+    assert!(buffer.ends_with(b"c"));
+
+    let mut parameters = parse_csi_parameters(buffer, 3, 1)?;
+    let class = parameters.remove(0);
+
+    Ok(Some(InternalEvent::PrimaryDeviceAttributes(
+        PrimaryDeviceAttributes {
+            class,
+            features: parameters,
+        },
+    )))
+}
+
+pub(crate) fn parse_csi_secondary_device_attributes(
+    buffer: &[u8],
+) -> Result<Option<InternalEvent>> {
+    // ESC [ > Ptype ; Pversion ; Pcartridge c
+    assert!(buffer.starts_with(b"\x1B[>")); // ESC [ >
+    assert!(buffer.ends_with(b"c"));
+
+    let parameters = parse_csi_parameters(buffer, 3, 1)?;
+
+    Ok(Some(InternalEvent::SecondaryDeviceAttributes(
+        SecondaryDeviceAttributes {
+            terminal_type: parameters[0],
+            version: parameters.get(1).copied().unwrap_or(0),
+            rom_cartridge: parameters.get(2).copied().unwrap_or(0),
+        },
+    )))
+}
+
+pub(crate) fn parse_csi_mode_report(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ Pa ; Ps $ y (ANSI mode) or ESC [ ? Pd ; Ps $ y (DEC private mode)
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"$y"));
+
+    let private = buffer[2] == b'?';
+    let parameters = parse_csi_parameters(buffer, if private { 3 } else { 2 }, 2)?;
+
+    if parameters.len() != 2 {
+        return Err(could_not_parse_event_error());
+    }
+
+    let mode = if private {
+        TerminalMode::Dec(parameters[0])
+    } else {
+        TerminalMode::Ansi(parameters[0])
+    };
+
+    let status = match parameters[1] {
+        0 => ModeStatus::NotRecognized,
+        1 => ModeStatus::Set,
+        2 => ModeStatus::Reset,
+        3 => ModeStatus::PermanentlySet,
+        4 => ModeStatus::PermanentlyReset,
+        _ => return Err(could_not_parse_event_error()),
+    };
+
+    Ok(Some(InternalEvent::ModeReport(mode, status)))
+}
+
 pub(crate) fn next_parsed<T>(iter: &mut dyn Iterator<Item = &str>) -> Result<T>
 where
     T: std::str::FromStr,
@@ -197,7 +435,7 @@ pub(crate) fn parse_csi_cursor_position(buffer: &[u8]) -> Result<Option<Internal
     Ok(Some(InternalEvent::CursorPosition(x, y)))
 }
 
-fn parse_modifiers(mask: u8) -> KeyModifiers {
+fn parse_modifiers(mask: u16) -> KeyModifiers {
     let modifier_mask = mask.saturating_sub(1);
     let mut modifiers = KeyModifiers::empty();
     if modifier_mask & 1 != 0 {
@@ -209,16 +447,82 @@ fn parse_modifiers(mask: u8) -> KeyModifiers {
     if modifier_mask & 4 != 0 {
         modifiers |= KeyModifiers::CONTROL;
     }
+    if modifier_mask & 8 != 0 {
+        modifiers |= KeyModifiers::SUPER;
+    }
+    if modifier_mask & 16 != 0 {
+        modifiers |= KeyModifiers::HYPER;
+    }
+    if modifier_mask & 32 != 0 {
+        modifiers |= KeyModifiers::META;
+    }
     modifiers
 }
 
+fn parse_modifiers_to_state(mask: u16) -> KeyEventState {
+    let modifier_mask = mask.saturating_sub(1);
+    let mut state = KeyEventState::empty();
+    if modifier_mask & 64 != 0 {
+        state |= KeyEventState::CAPS_LOCK;
+    }
+    if modifier_mask & 128 != 0 {
+        state |= KeyEventState::NUM_LOCK;
+    }
+    state
+}
+
+fn parse_key_event_kind(kind: u8) -> KeyEventKind {
+    match kind {
+        2 => KeyEventKind::Repeat,
+        3 => KeyEventKind::Release,
+        _ => KeyEventKind::Press,
+    }
+}
+
+// Parses the `modifiers[:event-type]` parameter, the event type is only sent by
+// terminals implementing the kitty keyboard protocol.
+fn modifier_and_kind_parsed(iter: &mut dyn Iterator<Item = &str>) -> Result<(u16, u8)> {
+    let mut sub_split = iter
+        .next()
+        .ok_or_else(could_not_parse_event_error)?
+        .split(':');
+
+    let modifier_mask = next_parsed::<u16>(&mut sub_split)?;
+
+    if let Ok(kind_code) = next_parsed::<u8>(&mut sub_split) {
+        Ok((modifier_mask, kind_code))
+    } else {
+        Ok((modifier_mask, 1))
+    }
+}
+
 pub(crate) fn parse_csi_modifier_key_code(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ 1 ; modifiers[:event-type] final
+    // ESC [ modifiers final
     assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
 
-    let modifier_mask = buffer[buffer.len() - 2];
-    let key = buffer[buffer.len() - 1];
+    let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+    let mut split = s.split(';');
+
+    // The key code is always `1` and there's no need to check it. Some terminals
+    // send just the modifier mask, without the leading `1;`.
+    if s.contains(';') {
+        split.next();
+    }
+
+    let (modifiers, kind, state) =
+        if let Ok((modifier_mask, kind_code)) = modifier_and_kind_parsed(&mut split) {
+            (
+                parse_modifiers(modifier_mask),
+                parse_key_event_kind(kind_code),
+                parse_modifiers_to_state(modifier_mask),
+            )
+        } else {
+            (KeyModifiers::NONE, KeyEventKind::Press, KeyEventState::NONE)
+        };
 
-    let modifiers = parse_modifiers(modifier_mask);
+    let key = buffer[buffer.len() - 1];
 
     let keycode = match key {
         b'A' => KeyCode::Up,
@@ -227,6 +531,7 @@ pub(crate) fn parse_csi_modifier_key_code(buffer: &[u8]) -> Result<Option<Intern
         b'D' => KeyCode::Left,
         b'F' => KeyCode::End,
         b'H' => KeyCode::Home,
+        b'E' => KeyCode::KeypadBegin,
         b'P' => KeyCode::F(1),
         b'Q' => KeyCode::F(2),
         b'R' => KeyCode::F(3),
@@ -234,14 +539,192 @@ pub(crate) fn parse_csi_modifier_key_code(buffer: &[u8]) -> Result<Option<Intern
         _ => return Err(could_not_parse_event_error()),
     };
 
-    let input_event = Event::Key(KeyEvent::new(keycode, modifiers));
+    let input_event = Event::Key(KeyEvent::new_with_kind_and_state(
+        keycode, modifiers, kind, state,
+    ));
+
+    Ok(Some(InternalEvent::Event(input_event)))
+}
+
+// Translates the unicode codepoint of a key reported with its modifiers (kitty keyboard
+// protocol, xterm modifyOtherKeys) to a key code.
+//
+// Uppercase characters and the SHIFT modifier are normalized the same way as by the
+// `char_code_to_event` function.
+fn translate_codepoint(
+    codepoint: u32,
+    shifted: Option<char>,
+    modifiers: &mut KeyModifiers,
+) -> Result<KeyCode> {
+    let mut keycode = match codepoint {
+        9 if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        9 => KeyCode::Tab,
+        13 => KeyCode::Enter,
+        27 => KeyCode::Esc,
+        127 => KeyCode::Backspace,
+        c => KeyCode::Char(std::char::from_u32(c).ok_or_else(could_not_parse_event_error)?),
+    };
+
+    if let KeyCode::Char(c) = keycode {
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            // Use the shifted key if available, fall back to the uppercase variant
+            // of the character otherwise.
+            if let Some(shifted) = shifted {
+                keycode = KeyCode::Char(shifted);
+            } else if c.is_lowercase() {
+                let mut upper = c.to_uppercase();
+                if let (Some(upper), None) = (upper.next(), upper.next()) {
+                    keycode = KeyCode::Char(upper);
+                }
+            }
+        } else if c.is_uppercase() {
+            // Mimic `char_code_to_event`, uppercase characters have the SHIFT modifier
+            *modifiers |= KeyModifiers::SHIFT;
+        }
+    }
+
+    Ok(keycode)
+}
+
+// Translates the kitty keyboard protocol functional key codes (Private Use Area),
+// see https://sw.kovidgoyal.net/kitty/keyboard-protocol/#functional-key-definitions
+fn translate_functional_key_code(codepoint: u32) -> Option<(KeyCode, KeyEventState)> {
+    let keypad = |code| Some((code, KeyEventState::KEYPAD));
+
+    let keycode = match codepoint {
+        57399..=57408 => {
+            return keypad(KeyCode::Char(
+                std::char::from_digit(codepoint - 57399, 10).unwrap(),
+            ))
+        }
+        57409 => return keypad(KeyCode::Char('.')),
+        57410 => return keypad(KeyCode::Char('/')),
+        57411 => return keypad(KeyCode::Char('*')),
+        57412 => return keypad(KeyCode::Char('-')),
+        57413 => return keypad(KeyCode::Char('+')),
+        57414 => return keypad(KeyCode::Enter),
+        57415 => return keypad(KeyCode::Char('=')),
+        57416 => return keypad(KeyCode::Char(',')),
+        57417 => return keypad(KeyCode::Left),
+        57418 => return keypad(KeyCode::Right),
+        57419 => return keypad(KeyCode::Up),
+        57420 => return keypad(KeyCode::Down),
+        57421 => return keypad(KeyCode::PageUp),
+        57422 => return keypad(KeyCode::PageDown),
+        57423 => return keypad(KeyCode::Home),
+        57424 => return keypad(KeyCode::End),
+        57425 => return keypad(KeyCode::Insert),
+        57426 => return keypad(KeyCode::Delete),
+        57427 => return keypad(KeyCode::KeypadBegin),
+        57358 => KeyCode::CapsLock,
+        57359 => KeyCode::ScrollLock,
+        57360 => KeyCode::NumLock,
+        57361 => KeyCode::PrintScreen,
+        57362 => KeyCode::Pause,
+        57363 => KeyCode::Menu,
+        // F13-F35
+        57376..=57398 => KeyCode::F((codepoint - 57376 + 13) as u8),
+        57428 => KeyCode::Media(MediaKeyCode::Play),
+        57429 => KeyCode::Media(MediaKeyCode::Pause),
+        57430 => KeyCode::Media(MediaKeyCode::PlayPause),
+        57431 => KeyCode::Media(MediaKeyCode::Reverse),
+        57432 => KeyCode::Media(MediaKeyCode::Stop),
+        57433 => KeyCode::Media(MediaKeyCode::FastForward),
+        57434 => KeyCode::Media(MediaKeyCode::Rewind),
+        57435 => KeyCode::Media(MediaKeyCode::TrackNext),
+        57436 => KeyCode::Media(MediaKeyCode::TrackPrevious),
+        57437 => KeyCode::Media(MediaKeyCode::Record),
+        57438 => KeyCode::Media(MediaKeyCode::LowerVolume),
+        57439 => KeyCode::Media(MediaKeyCode::RaiseVolume),
+        57440 => KeyCode::Media(MediaKeyCode::MuteVolume),
+        57441 => KeyCode::Modifier(ModifierKeyCode::LeftShift),
+        57442 => KeyCode::Modifier(ModifierKeyCode::LeftControl),
+        57443 => KeyCode::Modifier(ModifierKeyCode::LeftAlt),
+        57444 => KeyCode::Modifier(ModifierKeyCode::LeftSuper),
+        57445 => KeyCode::Modifier(ModifierKeyCode::LeftHyper),
+        57446 => KeyCode::Modifier(ModifierKeyCode::LeftMeta),
+        57447 => KeyCode::Modifier(ModifierKeyCode::RightShift),
+        57448 => KeyCode::Modifier(ModifierKeyCode::RightControl),
+        57449 => KeyCode::Modifier(ModifierKeyCode::RightAlt),
+        57450 => KeyCode::Modifier(ModifierKeyCode::RightSuper),
+        57451 => KeyCode::Modifier(ModifierKeyCode::RightHyper),
+        57452 => KeyCode::Modifier(ModifierKeyCode::RightMeta),
+        57453 => KeyCode::Modifier(ModifierKeyCode::IsoLevel3Shift),
+        57454 => KeyCode::Modifier(ModifierKeyCode::IsoLevel5Shift),
+        _ => return None,
+    };
+
+    Some((keycode, KeyEventState::empty()))
+}
+
+pub(crate) fn parse_csi_u_encoded_key_code(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // kitty keyboard protocol encoding:
+    // ESC [ unicode-key-code[:shifted-key[:base-layout-key]] ; modifiers[:event-type] [; text] u
+    //
+    // See https://sw.kovidgoyal.net/kitty/keyboard-protocol/
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"u"));
+
+    let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+    let mut split = s.split(';');
+
+    let mut codepoints = split
+        .next()
+        .ok_or_else(could_not_parse_event_error)?
+        .split(':');
+
+    let codepoint = next_parsed::<u32>(&mut codepoints)?;
+
+    let (mut modifiers, kind, state) =
+        if let Ok((modifier_mask, kind_code)) = modifier_and_kind_parsed(&mut split) {
+            (
+                parse_modifiers(modifier_mask),
+                parse_key_event_kind(kind_code),
+                parse_modifiers_to_state(modifier_mask),
+            )
+        } else {
+            (KeyModifiers::NONE, KeyEventKind::Press, KeyEventState::NONE)
+        };
+
+    let (keycode, state) =
+        if let Some((keycode, keypad_state)) = translate_functional_key_code(codepoint) {
+            (keycode, state | keypad_state)
+        } else {
+            // The shifted key is sent only if the `REPORT_ALTERNATE_KEYS` flag is set
+            let shifted = codepoints
+                .next()
+                .filter(|s| !s.is_empty())
+                .and_then(|s| s.parse::<u32>().ok())
+                .and_then(std::char::from_u32);
+
+            (
+                translate_codepoint(codepoint, shifted, &mut modifiers)?,
+                state,
+            )
+        };
+
+    let input_event = Event::Key(KeyEvent::new_with_kind_and_state(
+        keycode, modifiers, kind, state,
+    ));
 
     Ok(Some(InternalEvent::Event(input_event)))
 }
 
 pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ key-number [; modifiers[:event-type]] ~
+    //
+    // rxvt encodes the modifiers in the final byte instead:
+    // ESC [ key-number $ (shift), ^ (control), @ (control + shift)
     assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
-    assert!(buffer.ends_with(&[b'~']));
+
+    let final_modifiers = match buffer[buffer.len() - 1] {
+        b'~' => KeyModifiers::empty(),
+        b'$' => KeyModifiers::SHIFT,
+        b'^' => KeyModifiers::CONTROL,
+        b'@' => KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        _ => return Err(could_not_parse_event_error()),
+    };
 
     let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
         .map_err(|_| could_not_parse_event_error())?;
@@ -250,10 +733,20 @@ pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> Result<Option<Interna
     // This CSI sequence can be a list of semicolon-separated numbers.
     let first = next_parsed::<u8>(&mut split)?;
 
-    let modifiers = if let Ok(modifier_mask) = next_parsed::<u8>(&mut split) {
-        parse_modifiers(modifier_mask)
+    if first == 27 && final_modifiers.is_empty() {
+        return parse_csi_modify_other_keys(&mut split);
+    }
+
+    let (modifiers, kind, state) = if !final_modifiers.is_empty() {
+        (final_modifiers, KeyEventKind::Press, KeyEventState::NONE)
+    } else if let Ok((modifier_mask, kind_code)) = modifier_and_kind_parsed(&mut split) {
+        (
+            parse_modifiers(modifier_mask),
+            parse_key_event_kind(kind_code),
+            parse_modifiers_to_state(modifier_mask),
+        )
     } else {
-        KeyModifiers::NONE
+        (KeyModifiers::NONE, KeyEventKind::Press, KeyEventState::NONE)
     };
 
     let keycode = match first {
@@ -265,11 +758,37 @@ pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> Result<Option<Interna
         6 => KeyCode::PageDown,
         v @ 11..=15 => KeyCode::F(v - 10),
         v @ 17..=21 => KeyCode::F(v - 11),
-        v @ 23..=24 => KeyCode::F(v - 12),
+        v @ 23..=26 => KeyCode::F(v - 12),
+        v @ 28..=29 => KeyCode::F(v - 13),
+        v @ 31..=34 => KeyCode::F(v - 14),
         _ => return Err(could_not_parse_event_error()),
     };
 
-    let input_event = Event::Key(KeyEvent::new(keycode, modifiers));
+    let input_event = Event::Key(KeyEvent::new_with_kind_and_state(
+        keycode, modifiers, kind, state,
+    ));
+
+    Ok(Some(InternalEvent::Event(input_event)))
+}
+
+// xterm modifyOtherKeys encoding:
+// ESC [ 27 ; modifiers ; unicode-key-code ~
+fn parse_csi_modify_other_keys(
+    split: &mut dyn Iterator<Item = &str>,
+) -> Result<Option<InternalEvent>> {
+    let modifier_mask = next_parsed::<u16>(split)?;
+    let codepoint = next_parsed::<u32>(split)?;
+
+    let mut modifiers = parse_modifiers(modifier_mask);
+    let state = parse_modifiers_to_state(modifier_mask);
+    let keycode = translate_codepoint(codepoint, None, &mut modifiers)?;
+
+    let input_event = Event::Key(KeyEvent::new_with_kind_and_state(
+        keycode,
+        modifiers,
+        KeyEventKind::Press,
+        state,
+    ));
 
     Ok(Some(InternalEvent::Event(input_event)))
 }
@@ -285,54 +804,27 @@ pub(crate) fn parse_csi_rxvt_mouse(buffer: &[u8]) -> Result<Option<InternalEvent
         .map_err(|_| could_not_parse_event_error())?;
     let mut split = s.split(';');
 
-    let cb = next_parsed::<u16>(&mut split)?;
-    let cx = next_parsed::<u16>(&mut split)? - 1;
-    let cy = next_parsed::<u16>(&mut split)? - 1;
+    // Cb is sent with the same 32 offset as in the X10 encoding
+    let cb = next_parsed::<u8>(&mut split)?
+        .checked_sub(32)
+        .ok_or_else(could_not_parse_event_error)?;
+    let cx = next_parsed::<u16>(&mut split)?
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
+    let cy = next_parsed::<u16>(&mut split)?
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
 
-    let mut modifiers = KeyModifiers::empty();
-
-    if cb & 0b0000_0100 == 0b0000_0100 {
-        modifiers |= KeyModifiers::SHIFT;
-    }
-
-    if cb & 0b0000_1000 == 0b0000_1000 {
-        modifiers |= KeyModifiers::ALT;
-    }
-
-    if cb & 0b0001_0000 == 0b0001_0000 {
-        modifiers |= KeyModifiers::CONTROL;
-    }
-
-    let event = if cb & 0b0110_0000 == 0b0110_0000 {
-        if cb & 0b0000_0001 == 0b0000_0001 {
-            MouseEvent::ScrollDown(cx, cy, modifiers)
-        } else {
-            MouseEvent::ScrollUp(cx, cy, modifiers)
-        }
-    } else {
-        let drag = cb & 0b0100_0000 == 0b0100_0000;
-
-        match (cb & 0b0000_0011, drag) {
-            (0b0000_0000, false) => MouseEvent::Down(MouseButton::Left, cx, cy, modifiers),
-            (0b0000_0010, false) => MouseEvent::Down(MouseButton::Right, cx, cy, modifiers),
-            (0b0000_0001, false) => MouseEvent::Down(MouseButton::Middle, cx, cy, modifiers),
-
-            (0b0000_0000, true) => MouseEvent::Drag(MouseButton::Left, cx, cy, modifiers),
-            (0b0000_0010, true) => MouseEvent::Drag(MouseButton::Right, cx, cy, modifiers),
-            (0b0000_0001, true) => MouseEvent::Drag(MouseButton::Middle, cx, cy, modifiers),
-
-            (0b0000_0011, false) => MouseEvent::Up(MouseButton::Left, cx, cy, modifiers),
-
-            _ => return Err(could_not_parse_event_error()),
-        }
-    };
+    let event = parse_cb(cb, cx, cy)?;
 
     Ok(Some(InternalEvent::Event(Event::Mouse(event))))
 }
 
 pub(crate) fn parse_csi_x10_mouse(buffer: &[u8]) -> Result<Option<InternalEvent>> {
     // X10 emulation mouse encoding: ESC [ M CB Cx Cy (6 characters only).
-    // NOTE (@imdaveho): cannot find documentation on this
+    // All three values are sent as single bytes with a 32 offset.
+    //
+    // See http://www.xfree86.org/current/ctlseqs.html#Mouse%20Tracking
 
     assert!(buffer.starts_with(&[b'\x1B', b'[', b'M'])); // ESC [ M
 
@@ -340,46 +832,20 @@ pub(crate) fn parse_csi_x10_mouse(buffer: &[u8]) -> Result<Option<InternalEvent>
         return Ok(None);
     }
 
-    let cb = buffer[3] - 0x30;
+    let cb = buffer[3]
+        .checked_sub(32)
+        .ok_or_else(could_not_parse_event_error)?;
     // See http://www.xfree86.org/current/ctlseqs.html#Mouse%20Tracking
     // The upper left character position on the terminal is denoted as 1,1.
     // Subtract 1 to keep it synced with cursor
-    let cx = u16::from(buffer[4].saturating_sub(32)) - 1;
-    let cy = u16::from(buffer[5].saturating_sub(32)) - 1;
-
-    let mut modifiers = KeyModifiers::empty();
-
-    if cb & 0b0000_0100 == 0b0000_0100 {
-        modifiers |= KeyModifiers::SHIFT;
-    }
-
-    if cb & 0b0000_1000 == 0b0000_1000 {
-        modifiers |= KeyModifiers::ALT;
-    }
-
-    if cb & 0b0001_0000 == 0b0001_0000 {
-        modifiers |= KeyModifiers::CONTROL;
-    }
+    let cx = u16::from(buffer[4].saturating_sub(32))
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
+    let cy = u16::from(buffer[5].saturating_sub(32))
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
 
-    let mouse_input_event = match cb & 0b0000_0011 {
-        0 => {
-            if cb & 0b0100_0000 == 0b0100_0000 {
-                MouseEvent::ScrollUp(cx, cy, modifiers)
-            } else {
-                MouseEvent::Down(MouseButton::Left, cx, cy, modifiers)
-            }
-        }
-        1 => {
-            if cb & 0b0100_0000 == 0b0100_0000 {
-                MouseEvent::ScrollDown(cx, cy, modifiers)
-            } else {
-                MouseEvent::Down(MouseButton::Middle, cx, cy, modifiers)
-            }
-        }
-        2 => MouseEvent::Down(MouseButton::Right, cx, cy, modifiers),
-        3 => MouseEvent::Up(MouseButton::Left, cx, cy, modifiers),
-        _ => return Err(could_not_parse_event_error()),
-    };
+    let mouse_input_event = parse_cb(cb, cx, cy)?;
 
     Ok(Some(InternalEvent::Event(Event::Mouse(mouse_input_event))))
 }
@@ -397,13 +863,53 @@ pub(crate) fn parse_csi_xterm_mouse(buffer: &[u8]) -> Result<Option<InternalEven
         .map_err(|_| could_not_parse_event_error())?;
     let mut split = s.split(';');
 
-    let cb = next_parsed::<u16>(&mut split)?;
+    let cb = next_parsed::<u8>(&mut split)?;
 
     // See http://www.xfree86.org/current/ctlseqs.html#Mouse%20Tracking
     // The upper left character position on the terminal is denoted as 1,1.
     // Subtract 1 to keep it synced with cursor
-    let cx = next_parsed::<u16>(&mut split)? - 1;
-    let cy = next_parsed::<u16>(&mut split)? - 1;
+    let cx = next_parsed::<u16>(&mut split)?
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
+    let cy = next_parsed::<u16>(&mut split)?
+        .checked_sub(1)
+        .ok_or_else(could_not_parse_event_error)?;
+
+    let event = parse_cb(cb, cx, cy)?;
+
+    // The SGR encoding reports the released button, the final character
+    // is the only difference between the press and release reports.
+    let event = if buffer.ends_with(b"m") {
+        match event {
+            MouseEvent::Down(button, cx, cy, modifiers)
+            | MouseEvent::Drag(button, cx, cy, modifiers) => {
+                MouseEvent::Up(button, cx, cy, modifiers)
+            }
+            event => event,
+        }
+    } else {
+        event
+    };
+
+    Ok(Some(InternalEvent::Event(Event::Mouse(event))))
+}
+
+/// Parses the `Cb` parameter of a mouse report.
+///
+/// `Cb` contains the button being used, the key modifiers being held and whether
+/// the mouse is moving or not. The bit layout, from low to high, is:
+///
+/// * button number
+/// * button number
+/// * shift
+/// * meta (alt)
+/// * control
+/// * mouse is moving
+/// * button number
+/// * button number
+fn parse_cb(cb: u8, cx: u16, cy: u16) -> Result<MouseEvent> {
+    let button_number = (cb & 0b0000_0011) | ((cb & 0b1100_0000) >> 4);
+    let moving = cb & 0b0010_0000 == 0b0010_0000;
 
     let mut modifiers = KeyModifiers::empty();
 
@@ -419,36 +925,49 @@ pub(crate) fn parse_csi_xterm_mouse(buffer: &[u8]) -> Result<Option<InternalEven
         modifiers |= KeyModifiers::CONTROL;
     }
 
-    let event = if cb & 0b0100_0000 == 0b0100_0000 {
-        if cb & 0b0000_0001 == 0b0000_0001 {
-            MouseEvent::ScrollDown(cx, cy, modifiers)
-        } else {
-            MouseEvent::ScrollUp(cx, cy, modifiers)
+    let event = match (button_number, moving) {
+        (0..=2, false) | (8..=11, false) => {
+            MouseEvent::Down(parse_button(button_number), cx, cy, modifiers)
         }
-    } else {
-        let up = match buffer.last().unwrap() {
-            b'm' => true,
-            b'M' => false,
-            _ => return Err(could_not_parse_event_error()),
-        };
-
-        let drag = cb & 0b0010_0000 == 0b0010_0000;
-
-        match (cb & 0b0000_0011, up, drag) {
-            (0, true, _) => MouseEvent::Up(MouseButton::Left, cx, cy, modifiers),
-            (0, false, false) => MouseEvent::Down(MouseButton::Left, cx, cy, modifiers),
-            (0, false, true) => MouseEvent::Drag(MouseButton::Left, cx, cy, modifiers),
-            (1, true, _) => MouseEvent::Up(MouseButton::Middle, cx, cy, modifiers),
-            (1, false, false) => MouseEvent::Down(MouseButton::Middle, cx, cy, modifiers),
-            (1, false, true) => MouseEvent::Drag(MouseButton::Middle, cx, cy, modifiers),
-            (2, true, _) => MouseEvent::Up(MouseButton::Right, cx, cy, modifiers),
-            (2, false, false) => MouseEvent::Down(MouseButton::Right, cx, cy, modifiers),
-            (2, false, true) => MouseEvent::Drag(MouseButton::Right, cx, cy, modifiers),
-            _ => return Err(could_not_parse_event_error()),
+        (0..=2, true) | (8..=11, true) => {
+            MouseEvent::Drag(parse_button(button_number), cx, cy, modifiers)
         }
+        // Button number 3 means "no button" (released) in the legacy encodings
+        (3, false) => MouseEvent::Up(MouseButton::Left, cx, cy, modifiers),
+        (3..=7, true) => MouseEvent::Moved(cx, cy, modifiers),
+        (4, false) => MouseEvent::ScrollUp(cx, cy, modifiers),
+        (5, false) => MouseEvent::ScrollDown(cx, cy, modifiers),
+        (6, false) => MouseEvent::ScrollLeft(cx, cy, modifiers),
+        (7, false) => MouseEvent::ScrollRight(cx, cy, modifiers),
+        _ => return Err(could_not_parse_event_error()),
     };
 
-    Ok(Some(InternalEvent::Event(Event::Mouse(event))))
+    Ok(event)
+}
+
+// Converts the button number from `parse_cb` to the `MouseButton`, button numbers
+// 8 and up match the X11 button numbers.
+fn parse_button(button_number: u8) -> MouseButton {
+    match button_number {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        2 => MouseButton::Right,
+        8 => MouseButton::Back,
+        9 => MouseButton::Forward,
+        n => MouseButton::Other(n),
+    }
+}
+
+pub(crate) fn parse_csi_bracketed_paste(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ 2 0 0 ~ pasted text ESC [ 2 0 1 ~
+    assert!(buffer.starts_with(b"\x1B[200~"));
+
+    if !buffer.ends_with(b"\x1B[201~") {
+        Ok(None)
+    } else {
+        let paste = String::from_utf8_lossy(&buffer[6..buffer.len() - 6]).to_string();
+        Ok(Some(InternalEvent::Event(Event::Paste(paste))))
+    }
 }
 
 pub(crate) fn parse_utf8_char(buffer: &[u8]) -> Result<Option<char>> {
@@ -496,6 +1015,11 @@ mod tests {
 
     use super::*;
 
+    // Parses the input without expecting the string sequences
+    fn parse_event(buffer: &[u8], input_available: bool) -> Result<Option<InternalEvent>> {
+        super::parse_event(buffer, input_available, false)
+    }
+
     #[test]
     fn test_esc_key() {
         assert_eq!(
@@ -521,57 +1045,238 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_event_subsequent_calls() {
-        // The main purpose of this test is to check if we're passing
-        // correct slice to other parse_ functions.
-
-        // parse_csi_cursor_position
+    fn test_parse_string_sequence_introducers_as_alt_keys() {
+        // String sequences aren't expected
         assert_eq!(
-            parse_event("\x1B[20;10R".as_bytes(), false).unwrap(),
-            Some(InternalEvent::CursorPosition(9, 19))
+            parse_event(b"\x1BP", true).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('P'),
+                KeyModifiers::ALT
+            )))),
         );
-
-        // parse_csi
         assert_eq!(
-            parse_event("\x1B[D".as_bytes(), false).unwrap(),
-            Some(InternalEvent::Event(Event::Key(KeyCode::Left.into()))),
+            parse_event(b"\x1B]", true).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char(']'),
+                KeyModifiers::ALT
+            )))),
         );
-
-        // parse_csi_modifier_key_code
         assert_eq!(
-            parse_event("\x1B[2D".as_bytes(), false).unwrap(),
+            super::parse_event(b"\x1B_", true, false).unwrap(),
             Some(InternalEvent::Event(Event::Key(KeyEvent::new(
-                KeyCode::Left,
-                KeyModifiers::SHIFT
-            ))))
+                KeyCode::Char('_'),
+                KeyModifiers::ALT
+            )))),
         );
+    }
 
-        // parse_csi_special_key_code
+    #[test]
+    fn test_parse_string_sequence() {
+        let parse_event =
+            |buffer: &[u8], input_available| super::parse_event(buffer, input_available, true);
+
+        // Alt key combinations without more input
         assert_eq!(
-            parse_event("\x1B[3~".as_bytes(), false).unwrap(),
-            Some(InternalEvent::Event(Event::Key(KeyCode::Delete.into()))),
+            parse_event(b"\x1B]", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char(']'),
+                KeyModifiers::ALT
+            )))),
         );
-
-        // parse_csi_rxvt_mouse
         assert_eq!(
-            parse_event("\x1B[32;30;40;M".as_bytes(), false).unwrap(),
-            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
-                MouseButton::Left,
-                29,
-                39,
-                KeyModifiers::empty(),
-            ))))
+            parse_event(b"\x1BP", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('P'),
+                KeyModifiers::ALT
+            )))),
         );
 
-        // parse_csi_x10_mouse
+        assert_eq!(parse_event(b"\x1B]", true).unwrap(), None);
+        assert_eq!(parse_event(b"\x1B]11;rgb:00", false).unwrap(), None);
+        assert_eq!(parse_event(b"\x1BP>|xterm(367)\x1B", false).unwrap(), None);
+
+        // Complete, but unknown sequences
+        assert!(parse_event(b"\x1B]52;c;aGVsbG8=\x07", false).is_err());
+        assert!(parse_event(b"\x1BP1$r0m\x1B\\", false).is_err());
+        assert!(parse_event(b"\x1B_Gi=1;OK\x1B\\", false).is_err());
+        // ESC which doesn't start the terminator
+        assert!(parse_event(b"\x1B]11\x1B[", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_query_responses() {
         assert_eq!(
-            parse_event("\x1B[M0\x60\x70".as_bytes(), false).unwrap(),
-            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
-                MouseButton::Left,
-                63,
-                79,
-                KeyModifiers::empty(),
-            ))))
+            parse_event(b"\x1B[?62;4;22c", false).unwrap(),
+            Some(InternalEvent::PrimaryDeviceAttributes(
+                PrimaryDeviceAttributes {
+                    class: 62,
+                    features: vec![4, 22],
+                }
+            )),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[?1;2c", false).unwrap(),
+            Some(InternalEvent::PrimaryDeviceAttributes(
+                PrimaryDeviceAttributes {
+                    class: 1,
+                    features: vec![2],
+                }
+            )),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[>41;367;0c", false).unwrap(),
+            Some(InternalEvent::SecondaryDeviceAttributes(
+                SecondaryDeviceAttributes {
+                    terminal_type: 41,
+                    version: 367,
+                    rom_cartridge: 0,
+                }
+            )),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[>1;10c", false).unwrap(),
+            Some(InternalEvent::SecondaryDeviceAttributes(
+                SecondaryDeviceAttributes {
+                    terminal_type: 1,
+                    version: 10,
+                    rom_cartridge: 0,
+                }
+            )),
+        );
+        assert_eq!(
+            super::parse_event(b"\x1BP>|xterm(367)\x1B\\", false, true).unwrap(),
+            Some(InternalEvent::TerminalVersion("xterm(367)".to_string())),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[?2004;1$y", false).unwrap(),
+            Some(InternalEvent::ModeReport(
+                TerminalMode::Dec(2004),
+                ModeStatus::Set
+            )),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[4;2$y", false).unwrap(),
+            Some(InternalEvent::ModeReport(
+                TerminalMode::Ansi(4),
+                ModeStatus::Reset
+            )),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[?2026;0$y", false).unwrap(),
+            Some(InternalEvent::ModeReport(
+                TerminalMode::Dec(2026),
+                ModeStatus::NotRecognized
+            )),
+        );
+
+        // Incomplete
+        assert_eq!(parse_event(b"\x1B[?62;4", false).unwrap(), None);
+        assert_eq!(parse_event(b"\x1B[?2004;1$", false).unwrap(), None);
+        assert_eq!(parse_event(b"\x1B[4;2$", false).unwrap(), None);
+
+        // Other responses
+        assert!(parse_event(b"\x1B[?1u", false).is_err());
+        assert!(parse_event(b"\x1B[?2004;5$y", false).is_err());
+        assert!(parse_event(b"\x1B[?c", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_osc_color() {
+        let parse_event =
+            |buffer: &[u8], input_available| super::parse_event(buffer, input_available, true);
+
+        assert_eq!(
+            parse_event(b"\x1B]11;rgb:ffff/8080/0000\x1B\\", false).unwrap(),
+            Some(InternalEvent::TerminalColor(
+                11,
+                Color::Rgb {
+                    r: 255,
+                    g: 128,
+                    b: 0
+                }
+            )),
+        );
+        assert_eq!(
+            parse_event(b"\x1B]10;rgb:ff/80/0\x07", false).unwrap(),
+            Some(InternalEvent::TerminalColor(
+                10,
+                Color::Rgb {
+                    r: 255,
+                    g: 128,
+                    b: 0
+                }
+            )),
+        );
+        assert_eq!(
+            parse_event(b"\x1B]12;rgba:ffff/ffff/ffff/ffff\x07", false).unwrap(),
+            Some(InternalEvent::TerminalColor(
+                12,
+                Color::Rgb {
+                    r: 255,
+                    g: 255,
+                    b: 255
+                }
+            )),
+        );
+
+        assert!(parse_event(b"\x1B]11;?\x07", false).is_err());
+        assert!(parse_event(b"\x1B]11;rgb:ffff/ffff\x07", false).is_err());
+        assert!(parse_event(b"\x1B]11;rgb:fffff/0/0\x07", false).is_err());
+        assert!(parse_event(b"\x1B]0;title\x07", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_event_subsequent_calls() {
+        // The main purpose of this test is to check if we're passing
+        // correct slice to other parse_ functions.
+
+        // parse_csi_cursor_position
+        assert_eq!(
+            parse_event("\x1B[20;10R".as_bytes(), false).unwrap(),
+            Some(InternalEvent::CursorPosition(9, 19))
+        );
+
+        // parse_csi
+        assert_eq!(
+            parse_event("\x1B[D".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Left.into()))),
+        );
+
+        // parse_csi_modifier_key_code
+        assert_eq!(
+            parse_event("\x1B[2D".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Left,
+                KeyModifiers::SHIFT
+            ))))
+        );
+
+        // parse_csi_special_key_code
+        assert_eq!(
+            parse_event("\x1B[3~".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Delete.into()))),
+        );
+
+        // parse_csi_rxvt_mouse
+        assert_eq!(
+            parse_event("\x1B[32;30;40;M".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
+                MouseButton::Left,
+                29,
+                39,
+                KeyModifiers::empty(),
+            ))))
+        );
+
+        // parse_csi_x10_mouse
+        assert_eq!(
+            parse_event("\x1B[M \x60\x70".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
+                MouseButton::Left,
+                63,
+                79,
+                KeyModifiers::empty(),
+            ))))
         );
 
         // parse_csi_xterm_mouse
@@ -619,6 +1324,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_csi_focus_events() {
+        assert_eq!(
+            parse_event("\x1B[I".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::FocusGained)),
+        );
+        assert_eq!(
+            parse_event("\x1B[O".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::FocusLost)),
+        );
+    }
+
     #[test]
     fn test_parse_csi_modifier_key_code() {
         assert_eq!(
@@ -636,10 +1353,11 @@ mod tests {
             parse_csi_special_key_code("\x1B[3~".as_bytes()).unwrap(),
             Some(InternalEvent::Event(Event::Key(KeyCode::Delete.into()))),
         );
+        assert!(parse_csi_special_key_code("\x1B[3x".as_bytes()).is_err());
     }
 
     #[test]
-    fn test_parse_csi_special_key_code_multiple_values_not_supported() {
+    fn test_parse_csi_special_key_code_with_modifiers() {
         assert_eq!(
             parse_csi_special_key_code("\x1B[3;2~".as_bytes()).unwrap(),
             Some(InternalEvent::Event(Event::Key(KeyEvent::new(
@@ -647,6 +1365,427 @@ mod tests {
                 KeyModifiers::SHIFT
             )))),
         );
+        assert_eq!(
+            parse_event(b"\x1B[3;5~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Delete,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[5;2~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::PageUp,
+                KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[2;3~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Insert,
+                KeyModifiers::ALT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[15;6~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::F(5),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[34;2~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::F(20),
+                KeyModifiers::SHIFT
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_special_key_code_rxvt_modifiers() {
+        assert_eq!(
+            parse_event(b"\x1B[3^", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Delete,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[5$", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::PageUp,
+                KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[6@", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::PageDown,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )))),
+        );
+        // `$` is an intermediate byte here, wait for the final one
+        assert_eq!(parse_event(b"\x1B[4;1$", true).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_csi_special_key_code_with_kind() {
+        assert_eq!(
+            parse_csi_special_key_code("\x1B[3;1:3~".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Delete,
+                KeyModifiers::NONE,
+                KeyEventKind::Release,
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_modifier_key_code_with_kind() {
+        assert_eq!(
+            parse_csi_modifier_key_code("\x1B[1;5:3A".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Up,
+                KeyModifiers::CONTROL,
+                KeyEventKind::Release,
+            )))),
+        );
+        assert_eq!(
+            parse_csi_modifier_key_code("\x1B[1;1:2B".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Down,
+                KeyModifiers::NONE,
+                KeyEventKind::Repeat,
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_u_encoded_key_code() {
+        assert_eq!(
+            parse_event("\x1B[97u".as_bytes(), false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Char('a').into()))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[105;5u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('i'),
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[27u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Esc.into()))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[13;2u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[9;2u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::BackTab,
+                KeyModifiers::SHIFT
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_u_encoded_key_code_with_shift() {
+        // Without the alternate key reporting
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[97;2u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('A'),
+                KeyModifiers::SHIFT
+            )))),
+        );
+        // With the alternate key reporting
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[49:33;2u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('!'),
+                KeyModifiers::SHIFT
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_u_encoded_key_code_with_kind() {
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[97;1:1u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('a'),
+                KeyModifiers::NONE,
+                KeyEventKind::Press,
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[97;1:2u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('a'),
+                KeyModifiers::NONE,
+                KeyEventKind::Repeat,
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code("\x1B[97;5:3u".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('a'),
+                KeyModifiers::CONTROL,
+                KeyEventKind::Release,
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_u_encoded_key_code_with_extra_modifiers() {
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97;9u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('a'),
+                KeyModifiers::SUPER
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97;17u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('a'),
+                KeyModifiers::HYPER
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97;37u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('a'),
+                KeyModifiers::META | KeyModifiers::CONTROL
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_u_encoded_key_code_with_lock_state() {
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97;65u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyEvent::new_with_kind_and_state(
+                    KeyCode::Char('a'),
+                    KeyModifiers::empty(),
+                    KeyEventKind::Press,
+                    KeyEventState::CAPS_LOCK,
+                )
+            ))),
+        );
+        // All modifiers and both locks, the mask doesn't fit into u8
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97;256:3u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyEvent::new_with_kind_and_state(
+                    KeyCode::Char('A'),
+                    KeyModifiers::all(),
+                    KeyEventKind::Release,
+                    KeyEventState::CAPS_LOCK | KeyEventState::NUM_LOCK,
+                )
+            ))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_modifier_key_code_with_lock_state() {
+        assert_eq!(
+            parse_csi_modifier_key_code(b"\x1B[1;130A").unwrap(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyEvent::new_with_kind_and_state(
+                    KeyCode::Up,
+                    KeyModifiers::SHIFT,
+                    KeyEventKind::Press,
+                    KeyEventState::NUM_LOCK,
+                )
+            ))),
+        );
+    }
+
+    #[test]
+    fn test_parse_ss3_keypad_and_cursor_keys() {
+        let keypad = |code| {
+            Some(InternalEvent::Event(Event::Key(
+                KeyEvent::new_with_kind_and_state(
+                    code,
+                    KeyModifiers::empty(),
+                    KeyEventKind::Press,
+                    KeyEventState::KEYPAD,
+                ),
+            )))
+        };
+
+        assert_eq!(
+            parse_event(b"\x1BOM", false).unwrap(),
+            keypad(KeyCode::Enter)
+        );
+        assert_eq!(
+            parse_event(b"\x1BOp", false).unwrap(),
+            keypad(KeyCode::Char('0'))
+        );
+        assert_eq!(
+            parse_event(b"\x1BOy", false).unwrap(),
+            keypad(KeyCode::Char('9'))
+        );
+        assert_eq!(
+            parse_event(b"\x1BOk", false).unwrap(),
+            keypad(KeyCode::Char('+'))
+        );
+        assert_eq!(
+            parse_event(b"\x1BOE", false).unwrap(),
+            keypad(KeyCode::KeypadBegin)
+        );
+        assert_eq!(
+            parse_event(b"\x1BOA", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Up.into())))
+        );
+        assert!(parse_event(b"\x1BOz", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_csi_keypad_begin() {
+        assert_eq!(
+            parse_event(b"\x1B[E", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyCode::KeypadBegin.into()
+            )))
+        );
+        assert_eq!(
+            parse_event(b"\x1B[1;5E", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::KeypadBegin,
+                KeyModifiers::CONTROL
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_special_key_code_high_function_keys() {
+        assert_eq!(
+            parse_csi_special_key_code(b"\x1B[25~").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::F(13).into())))
+        );
+        assert_eq!(
+            parse_csi_special_key_code(b"\x1B[29~").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::F(16).into())))
+        );
+        assert_eq!(
+            parse_csi_special_key_code(b"\x1B[34~").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::F(20).into())))
+        );
+        assert!(parse_csi_special_key_code(b"\x1B[27~").is_err());
+    }
+
+    #[test]
+    fn test_parse_csi_u_encoded_functional_keys() {
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57358u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::CapsLock.into())))
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57363u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Menu.into())))
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57376u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::F(13).into())))
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57398;5u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::F(35),
+                KeyModifiers::CONTROL
+            ))))
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57404u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyEvent::new_with_kind_and_state(
+                    KeyCode::Char('5'),
+                    KeyModifiers::empty(),
+                    KeyEventKind::Press,
+                    KeyEventState::KEYPAD,
+                )
+            )))
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57414;1:3u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyEvent::new_with_kind_and_state(
+                    KeyCode::Enter,
+                    KeyModifiers::empty(),
+                    KeyEventKind::Release,
+                    KeyEventState::KEYPAD,
+                )
+            )))
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57430u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyCode::Media(MediaKeyCode::PlayPause).into()
+            )))
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57441;2u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Modifier(ModifierKeyCode::LeftShift),
+                KeyModifiers::SHIFT
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_modify_other_keys() {
+        assert_eq!(
+            parse_event(b"\x1B[27;6;65~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('A'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[27;6;97~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('A'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[27;5;13~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[27;5;9~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Tab,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[27;2;9~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::BackTab,
+                KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[27;3;49~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('1'),
+                KeyModifiers::ALT
+            )))),
+        );
+        assert!(parse_event(b"\x1B[27;5~", false).is_err());
     }
 
     #[test]
@@ -665,7 +1804,7 @@ mod tests {
     #[test]
     fn test_parse_csi_x10_mouse() {
         assert_eq!(
-            parse_csi_x10_mouse("\x1B[M0\x60\x70".as_bytes()).unwrap(),
+            parse_csi_x10_mouse("\x1B[M \x60\x70".as_bytes()).unwrap(),
             Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
                 MouseButton::Left,
                 63,
@@ -715,6 +1854,210 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_csi_bracketed_paste() {
+        assert_eq!(
+            parse_event("\x1B[200~o".as_bytes(), true).unwrap(),
+            None,
+            "A partial bracketed paste isn't parsed"
+        );
+        assert_eq!(
+            parse_event("\x1B[200~o\x1B[2D".as_bytes(), true).unwrap(),
+            None,
+            "A partial bracketed paste containing another escape code isn't parsed"
+        );
+        assert_eq!(
+            parse_event("\x1B[200~o\x1B[2D\x1B[201~".as_bytes(), true).unwrap(),
+            Some(InternalEvent::Event(Event::Paste("o\x1B[2D".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_xterm_mouse_moved() {
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<35;20;10M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Moved(
+                19,
+                9,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<32;20;10M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Drag(
+                MouseButton::Left,
+                19,
+                9,
+                KeyModifiers::empty(),
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_xterm_mouse_pixels() {
+        // SGR-Pixels uses the SGR encoding, positions can exceed the usual cell range
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<0;1234;567M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
+                MouseButton::Left,
+                1233,
+                566,
+                KeyModifiers::empty(),
+            ))))
+        );
+
+        // Positions start at 1
+        assert!(parse_csi_xterm_mouse("\x1B[<0;0;5M".as_bytes()).is_err());
+        assert!(parse_csi_xterm_mouse("\x1B[<0;5;0M".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_csi_rxvt_mouse_moved() {
+        assert_eq!(
+            parse_csi_rxvt_mouse("\x1B[67;30;40M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Moved(
+                29,
+                39,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_rxvt_mouse("\x1B[83;30;40M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Moved(
+                29,
+                39,
+                KeyModifiers::CONTROL,
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_cb_scroll() {
+        assert_eq!(
+            parse_cb(64, 1, 2).unwrap(),
+            MouseEvent::ScrollUp(1, 2, KeyModifiers::empty())
+        );
+        assert_eq!(
+            parse_cb(65 | 0b0000_0100, 1, 2).unwrap(),
+            MouseEvent::ScrollDown(1, 2, KeyModifiers::SHIFT)
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_x10_mouse_buttons() {
+        assert_eq!(
+            parse_csi_x10_mouse("\x1B[M#\x60\x70".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Up(
+                MouseButton::Left,
+                63,
+                79,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_x10_mouse("\x1B[M2\x60\x70".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
+                MouseButton::Right,
+                63,
+                79,
+                KeyModifiers::CONTROL,
+            ))))
+        );
+        assert_eq!(
+            parse_csi_x10_mouse("\x1B[M`\x60\x70".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::ScrollUp(
+                63,
+                79,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_x10_mouse("\x1B[Mb\x60\x70".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::ScrollLeft(
+                63,
+                79,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_x10_mouse(b"\x1B[M\xA0\x60\x70").unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
+                MouseButton::Back,
+                63,
+                79,
+                KeyModifiers::empty(),
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_xterm_mouse_extra_buttons() {
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<66;20;10M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::ScrollLeft(
+                19,
+                9,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<67;20;10M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::ScrollRight(
+                19,
+                9,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<128;20;10M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
+                MouseButton::Back,
+                19,
+                9,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<129;20;10m".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Up(
+                MouseButton::Forward,
+                19,
+                9,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_xterm_mouse("\x1B[<162;20;10M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Drag(
+                MouseButton::Other(10),
+                19,
+                9,
+                KeyModifiers::empty(),
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_rxvt_mouse_extra_buttons() {
+        assert_eq!(
+            parse_csi_rxvt_mouse("\x1B[98;30;40M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::ScrollLeft(
+                29,
+                39,
+                KeyModifiers::empty(),
+            ))))
+        );
+        assert_eq!(
+            parse_csi_rxvt_mouse("\x1B[161;30;40M".as_bytes()).unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent::Down(
+                MouseButton::Forward,
+                29,
+                39,
+                KeyModifiers::empty(),
+            ))))
+        );
+    }
+
     #[test]
     fn test_utf8() {
         // https://www.php.net/manual/en/reference.pcre.pattern.modifiers.php#54805
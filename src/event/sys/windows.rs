@@ -6,7 +6,7 @@ use crossterm_winapi::{ConsoleMode, Handle};
 
 use lazy_static::lazy_static;
 
-use crate::Result;
+use crate::{event::MouseTrackingMode, Result};
 
 #[cfg(feature = "event-stream")]
 pub(crate) mod waker;
@@ -18,6 +18,9 @@ const ENABLE_MOUSE_MODE: u32 = 0x0010 | 0x0080 | 0x0008;
 
 lazy_static! {
     static ref ORIGINAL_CONSOLE_MODE: Mutex<Option<u32>> = Mutex::new(None);
+    // The console reports all mouse events, they're filtered by the selected mode
+    static ref MOUSE_TRACKING_MODE: Mutex<MouseTrackingMode> =
+        Mutex::new(MouseTrackingMode::ButtonEvent);
 }
 
 /// Initializes the default console color. It will will be skipped if it has already been initialized.
@@ -38,14 +41,20 @@ fn original_console_mode() -> u32 {
         .expect("Original console mode not set")
 }
 
-pub(crate) fn enable_mouse_capture() -> Result<()> {
+pub(crate) fn enable_mouse_capture(tracking_mode: MouseTrackingMode) -> Result<()> {
     let mode = ConsoleMode::from(Handle::current_in_handle()?);
     init_original_console_mode(mode.mode()?);
     mode.set_mode(ENABLE_MOUSE_MODE)?;
+    *MOUSE_TRACKING_MODE.lock().unwrap() = tracking_mode;
 
     Ok(())
 }
 
+/// Returns the mouse tracking mode selected when the mouse capture was enabled.
+pub(crate) fn mouse_tracking_mode() -> MouseTrackingMode {
+    *MOUSE_TRACKING_MODE.lock().unwrap()
+}
+
 pub(crate) fn disable_mouse_capture() -> Result<()> {
     let mode = ConsoleMode::from(Handle::current_in_handle()?);
     mode.set_mode(original_console_mode())?;
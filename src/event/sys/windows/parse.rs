@@ -1,19 +1,28 @@
 use crossterm_winapi::{ControlKeyState, EventFlags, KeyEventRecord, MouseEvent, ScreenBuffer};
 use winapi::um::{
     wincon::{
-        LEFT_ALT_PRESSED, LEFT_CTRL_PRESSED, RIGHT_ALT_PRESSED, RIGHT_CTRL_PRESSED, SHIFT_PRESSED,
+        CAPSLOCK_ON, LEFT_ALT_PRESSED, LEFT_CTRL_PRESSED, NUMLOCK_ON, RIGHT_ALT_PRESSED,
+        RIGHT_CTRL_PRESSED, SHIFT_PRESSED,
     },
     winuser::{
-        VK_BACK, VK_CONTROL, VK_DELETE, VK_DOWN, VK_END, VK_ESCAPE, VK_F1, VK_F24, VK_HOME,
-        VK_INSERT, VK_LEFT, VK_MENU, VK_NEXT, VK_PRIOR, VK_RETURN, VK_RIGHT, VK_SHIFT, VK_UP,
+        VK_APPS, VK_BACK, VK_CAPITAL, VK_CLEAR, VK_CONTROL, VK_DELETE, VK_DIVIDE, VK_DOWN, VK_END,
+        VK_ESCAPE, VK_F1, VK_F24, VK_HOME, VK_INSERT, VK_LEFT, VK_MEDIA_NEXT_TRACK,
+        VK_MEDIA_PLAY_PAUSE, VK_MEDIA_PREV_TRACK, VK_MEDIA_STOP, VK_MENU, VK_NEXT, VK_NUMLOCK,
+        VK_NUMPAD0, VK_PAUSE, VK_PRIOR, VK_RETURN, VK_RIGHT, VK_SCROLL, VK_SHIFT, VK_SNAPSHOT,
+        VK_UP, VK_VOLUME_DOWN, VK_VOLUME_MUTE, VK_VOLUME_UP,
     },
 };
 
 use crate::{
-    event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton},
+    event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MediaKeyCode,
+        MouseButton, MouseTrackingMode,
+    },
     Result,
 };
 
+use super::mouse_tracking_mode;
+
 pub(crate) fn handle_mouse_event(mouse_event: MouseEvent) -> Result<Option<Event>> {
     if let Ok(Some(event)) = parse_mouse_event_record(&mouse_event) {
         return Ok(Some(Event::Mouse(event)));
@@ -53,6 +62,21 @@ impl From<ControlKeyState> for KeyModifiers {
     }
 }
 
+impl From<ControlKeyState> for KeyEventState {
+    fn from(state: ControlKeyState) -> Self {
+        let mut key_event_state = KeyEventState::empty();
+
+        if state.has_state(CAPSLOCK_ON) {
+            key_event_state |= KeyEventState::CAPS_LOCK;
+        }
+        if state.has_state(NUMLOCK_ON) {
+            key_event_state |= KeyEventState::NUM_LOCK;
+        }
+
+        key_event_state
+    }
+}
+
 fn parse_key_event_record(key_event: &KeyEventRecord) -> Option<KeyEvent> {
     let modifiers = KeyModifiers::from(key_event.control_key_state);
 
@@ -74,6 +98,20 @@ fn parse_key_event_record(key_event: &KeyEventRecord) -> Option<KeyEvent> {
         VK_END => Some(KeyCode::End),
         VK_DELETE => Some(KeyCode::Delete),
         VK_INSERT => Some(KeyCode::Insert),
+        VK_CAPITAL => Some(KeyCode::CapsLock),
+        VK_SCROLL => Some(KeyCode::ScrollLock),
+        VK_NUMLOCK => Some(KeyCode::NumLock),
+        VK_SNAPSHOT => Some(KeyCode::PrintScreen),
+        VK_PAUSE => Some(KeyCode::Pause),
+        VK_APPS => Some(KeyCode::Menu),
+        VK_CLEAR => Some(KeyCode::KeypadBegin),
+        VK_MEDIA_PLAY_PAUSE => Some(KeyCode::Media(MediaKeyCode::PlayPause)),
+        VK_MEDIA_STOP => Some(KeyCode::Media(MediaKeyCode::Stop)),
+        VK_MEDIA_NEXT_TRACK => Some(KeyCode::Media(MediaKeyCode::TrackNext)),
+        VK_MEDIA_PREV_TRACK => Some(KeyCode::Media(MediaKeyCode::TrackPrevious)),
+        VK_VOLUME_DOWN => Some(KeyCode::Media(MediaKeyCode::LowerVolume)),
+        VK_VOLUME_UP => Some(KeyCode::Media(MediaKeyCode::RaiseVolume)),
+        VK_VOLUME_MUTE => Some(KeyCode::Media(MediaKeyCode::MuteVolume)),
         _ => {
             // Modifier Keys (Ctrl, Alt, Shift) Support
             let character_raw = key_event.u_char;
@@ -113,8 +151,20 @@ fn parse_key_event_record(key_event: &KeyEventRecord) -> Option<KeyEvent> {
         }
     };
 
+    let mut state = KeyEventState::from(key_event.control_key_state);
+
+    // Numpad digits & operators
+    if (VK_NUMPAD0..=VK_DIVIDE).contains(&key_code) || key_code == VK_CLEAR {
+        state |= KeyEventState::KEYPAD;
+    }
+
     if let Some(key_code) = parse_result {
-        return Some(KeyEvent::new(key_code, modifiers));
+        return Some(KeyEvent::new_with_kind_and_state(
+            key_code,
+            modifiers,
+            KeyEventKind::Press,
+            state,
+        ));
     }
 
     None
@@ -158,18 +208,17 @@ fn parse_mouse_event_record(event: &MouseEvent) -> Result<Option<crate::event::M
                 ))
             }
         }
-        EventFlags::MouseMoved => {
-            // Click + Move
-            // Only register when mouse is not released
-            // because unix systems share this behaviour.
-            if !button_state.release_button() {
-                Some(crate::event::MouseEvent::Drag(
-                    button, xpos, ypos, modifiers,
-                ))
-            } else {
-                None
+        // The console reports all motion, it's filtered the same way UNIX terminals do
+        // in the selected mode
+        EventFlags::MouseMoved => match (button_state.release_button(), mouse_tracking_mode()) {
+            (true, MouseTrackingMode::AnyEvent) => {
+                Some(crate::event::MouseEvent::Moved(xpos, ypos, modifiers))
             }
-        }
+            (true, _) | (false, MouseTrackingMode::Normal) => None,
+            (false, _) => Some(crate::event::MouseEvent::Drag(
+                button, xpos, ypos, modifiers,
+            )),
+        },
         EventFlags::MouseWheeled => {
             // Vertical scroll
             // from https://docs.microsoft.com/en-us/windows/console/mouse-event-record-str
@@ -183,6 +232,16 @@ fn parse_mouse_event_record(event: &MouseEvent) -> Result<Option<crate::event::M
             }
         }
         EventFlags::DoubleClick => None, // double click not supported by unix terminals
-        EventFlags::MouseHwheeled => None, // horizontal scroll not supported by unix terminals
+        EventFlags::MouseHwheeled => {
+            // Horizontal scroll
+            // if `button_state` is negative then the wheel was rotated to the left.
+            if button_state.scroll_down() {
+                Some(crate::event::MouseEvent::ScrollLeft(xpos, ypos, modifiers))
+            } else if button_state.scroll_up() {
+                Some(crate::event::MouseEvent::ScrollRight(xpos, ypos, modifiers))
+            } else {
+                None
+            }
+        }
     })
 }
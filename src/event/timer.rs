@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+
+/// One-shot and periodic timers of an event reader, identified by the user given ids.
+#[derive(Debug, Default)]
+pub(crate) struct Timers {
+    timers: Vec<Timer>,
+}
+
+#[derive(Debug)]
+struct Timer {
+    id: usize,
+    deadline: Instant,
+    // `None` for one-shot timers
+    interval: Option<Duration>,
+}
+
+impl Timers {
+    /// Adds a timer expiring after the `delay` and then every `interval` if it's periodic.
+    ///
+    /// A timer with the same id is replaced.
+    pub(crate) fn insert(&mut self, id: usize, delay: Duration, interval: Option<Duration>) {
+        self.remove(id);
+        self.timers.push(Timer {
+            id,
+            deadline: Instant::now() + delay,
+            interval,
+        });
+    }
+
+    /// Removes the timer, returns `false` if there's no timer with the given id.
+    pub(crate) fn remove(&mut self, id: usize) -> bool {
+        let count = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != count
+    }
+
+    /// Returns the time left before the nearest timer expires, `None` if there are no timers.
+    pub(crate) fn leftover(&self) -> Option<Duration> {
+        self.timers
+            .iter()
+            .map(|timer| timer.deadline.saturating_duration_since(Instant::now()))
+            .min()
+    }
+
+    /// Returns the id of the expired timer with the earliest deadline.
+    ///
+    /// One-shot timers are removed, periodic ones are rescheduled. Missed ticks of a periodic
+    /// timer are reported just once, the next tick of a late timer is one interval from now.
+    pub(crate) fn expire(&mut self) -> Option<usize> {
+        let now = Instant::now();
+        let index = self
+            .timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.deadline <= now)
+            .min_by_key(|(_, timer)| timer.deadline)
+            .map(|(index, _)| index)?;
+
+        let timer = &mut self.timers[index];
+        let id = timer.id;
+
+        match timer.interval {
+            Some(interval) => {
+                timer.deadline += interval;
+                if timer.deadline <= now {
+                    timer.deadline = now + interval;
+                }
+            }
+            None => {
+                self.timers.remove(index);
+            }
+        }
+
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Timers;
+
+    #[test]
+    fn test_one_shot_timer() {
+        let mut timers = Timers::default();
+        assert_eq!(timers.leftover(), None);
+
+        timers.insert(1, Duration::from_secs(60), None);
+        timers.insert(2, Duration::from_secs(0), None);
+
+        assert_eq!(timers.leftover(), Some(Duration::from_secs(0)));
+        assert_eq!(timers.expire(), Some(2));
+        assert_eq!(timers.expire(), None);
+        assert!(timers.leftover().unwrap() > Duration::from_secs(59));
+
+        assert!(timers.remove(1));
+        assert!(!timers.remove(1));
+        assert_eq!(timers.leftover(), None);
+    }
+
+    #[test]
+    fn test_periodic_timer() {
+        let mut timers = Timers::default();
+        timers.insert(1, Duration::from_secs(0), Some(Duration::from_secs(60)));
+
+        assert_eq!(timers.expire(), Some(1));
+        assert_eq!(timers.expire(), None);
+        assert!(timers.leftover().unwrap() > Duration::from_secs(59));
+
+        // Replaced by a timer with the same id
+        timers.insert(1, Duration::from_secs(0), Some(Duration::from_secs(60)));
+        assert_eq!(timers.expire(), Some(1));
+        assert_eq!(timers.expire(), None);
+    }
+
+    #[test]
+    fn test_late_periodic_timer() {
+        let mut timers = Timers::default();
+        timers.insert(1, Duration::from_secs(0), Some(Duration::from_millis(10)));
+        std::thread::sleep(Duration::from_millis(30));
+
+        // Missed ticks are reported once, the next one isn't due immediately
+        assert_eq!(timers.expire(), Some(1));
+        assert_eq!(timers.expire(), None);
+        assert!(timers.leftover().unwrap() > Duration::from_millis(5));
+    }
+}
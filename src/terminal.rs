@@ -81,6 +81,9 @@
 //!
 //! For manual execution control check out [crossterm::queue](../macro.queue.html).
 
+#[cfg(unix)]
+use std::io::Write;
+
 #[cfg(windows)]
 use crossterm_winapi::{ConsoleMode, Handle, ScreenBuffer};
 #[cfg(feature = "serde")]
@@ -90,7 +93,21 @@ use winapi::um::wincon::ENABLE_WRAP_AT_EOL_OUTPUT;
 
 #[doc(no_inline)]
 use crate::Command;
-use crate::{impl_display, Result};
+#[cfg(unix)]
+use crate::{
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableModifyOtherKeys, DisableMouseCapture,
+        EnableBracketedPaste, EnableFocusChange, EnableModifyOtherKeys, EnableMouseTracking,
+        EnablePixelMouseCapture, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    queue,
+};
+use crate::{
+    event::{KeyboardEnhancementFlags, MouseTrackingMode},
+    impl_display,
+    style::Color,
+    Result,
+};
 
 mod ansi;
 pub(crate) mod sys;
@@ -109,6 +126,138 @@ pub fn disable_raw_mode() -> Result<()> {
     sys::disable_raw_mode()
 }
 
+/// Suspends the process like the `Ctrl+Z` key outside of the raw mode does.
+///
+/// The raw mode is disabled and the [`SuspendModes`](struct.SuspendModes.html) of the
+/// suspend handling are restored before the process group is stopped, everything is
+/// reapplied once the shell continues the process. Readers handling the suspension report
+/// [`Event::Suspend`](../event/enum.Event.html#variant.Suspend) and
+/// [`Event::Resume`](../event/enum.Event.html#variant.Resume) afterwards, redraw the screen
+/// when you receive the latter.
+///
+/// `SIGTSTP` signals (`Ctrl+Z` outside of the raw mode, `kill -TSTP`) are handled the same
+/// way once the suspend handling is enabled with
+/// [`EventReader::set_suspend_handling`](../event/struct.EventReader.html#method.set_suspend_handling).
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{
+///     event::{read, set_suspend_handling, Event, KeyCode, KeyEvent, KeyModifiers},
+///     terminal::{suspend, SuspendModes},
+///     Result,
+/// };
+///
+/// fn run() -> Result<()> {
+///     set_suspend_handling(Some(SuspendModes {
+///         alternate_screen: true,
+///         ..SuspendModes::default()
+///     }))?;
+///
+///     loop {
+///         match read()? {
+///             Event::Key(KeyEvent {
+///                 code: KeyCode::Char('z'),
+///                 modifiers: KeyModifiers::CONTROL,
+///                 ..
+///             }) => suspend()?,
+///             Event::Resume => { /* redraw */ }
+///             _ => {}
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Notes
+///
+/// This function is not supported on Windows.
+pub fn suspend() -> Result<()> {
+    sys::suspend()
+}
+
+/// The terminal modes enabled by the application, which are restored when the process
+/// is suspended and reapplied when it continues.
+///
+/// The raw mode is always restored, it's tracked by the
+/// [`enable_raw_mode`](fn.enable_raw_mode.html) function. Keep the modes up to date with
+/// [`EventReader::set_suspend_handling`](../event/struct.EventReader.html#method.set_suspend_handling)
+/// when the application enables or disables them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SuspendModes {
+    /// The alternate screen was entered with
+    /// [`EnterAlternateScreen`](struct.EnterAlternateScreen.html).
+    pub alternate_screen: bool,
+    /// The mouse capture was enabled with the given tracking mode, see
+    /// [`EnableMouseTracking`](../event/struct.EnableMouseTracking.html).
+    pub mouse_capture: Option<MouseTrackingMode>,
+    /// The mouse capture was enabled with
+    /// [`EnablePixelMouseCapture`](../event/struct.EnablePixelMouseCapture.html).
+    pub pixel_mouse_capture: bool,
+    /// The focus change events were enabled with
+    /// [`EnableFocusChange`](../event/struct.EnableFocusChange.html).
+    pub focus_change: bool,
+    /// The bracketed paste was enabled with
+    /// [`EnableBracketedPaste`](../event/struct.EnableBracketedPaste.html).
+    pub bracketed_paste: bool,
+    /// The `modifyOtherKeys` mode was enabled with
+    /// [`EnableModifyOtherKeys`](../event/struct.EnableModifyOtherKeys.html).
+    pub modify_other_keys: bool,
+    /// The flags were pushed with
+    /// [`PushKeyboardEnhancementFlags`](../event/struct.PushKeyboardEnhancementFlags.html).
+    pub keyboard_enhancement_flags: Option<KeyboardEnhancementFlags>,
+}
+
+#[cfg(unix)]
+impl SuspendModes {
+    /// Writes the commands disabling the modes, in the reverse order of `enable`.
+    pub(crate) fn disable(&self, writer: &mut impl Write) -> Result<()> {
+        if self.keyboard_enhancement_flags.is_some() {
+            queue!(writer, PopKeyboardEnhancementFlags)?;
+        }
+        if self.modify_other_keys {
+            queue!(writer, DisableModifyOtherKeys)?;
+        }
+        if self.bracketed_paste {
+            queue!(writer, DisableBracketedPaste)?;
+        }
+        if self.focus_change {
+            queue!(writer, DisableFocusChange)?;
+        }
+        if self.mouse_capture.is_some() || self.pixel_mouse_capture {
+            queue!(writer, DisableMouseCapture)?;
+        }
+        if self.alternate_screen {
+            queue!(writer, LeaveAlternateScreen)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the commands enabling the modes.
+    pub(crate) fn enable(&self, writer: &mut impl Write) -> Result<()> {
+        if self.alternate_screen {
+            queue!(writer, EnterAlternateScreen)?;
+        }
+        if self.pixel_mouse_capture {
+            queue!(writer, EnablePixelMouseCapture)?;
+        } else if let Some(mode) = self.mouse_capture {
+            queue!(writer, EnableMouseTracking(mode))?;
+        }
+        if self.focus_change {
+            queue!(writer, EnableFocusChange)?;
+        }
+        if self.bracketed_paste {
+            queue!(writer, EnableBracketedPaste)?;
+        }
+        if self.modify_other_keys {
+            queue!(writer, EnableModifyOtherKeys)?;
+        }
+        if let Some(flags) = self.keyboard_enhancement_flags {
+            queue!(writer, PushKeyboardEnhancementFlags(flags))?;
+        }
+        Ok(())
+    }
+}
+
 /// Returns the terminal size `(columns, rows)`.
 ///
 /// The top left cell is represented `(1, 1)`.
@@ -116,6 +265,227 @@ pub fn size() -> Result<(u16, u16)> {
     sys::size()
 }
 
+/// Represents the size of the terminal window.
+///
+/// See the [`window_size`](fn.window_size.html) function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WindowSize {
+    /// Number of rows (cells).
+    pub rows: u16,
+    /// Number of columns (cells).
+    pub columns: u16,
+    /// Width of the window in pixels, `0` if unknown.
+    pub width: u16,
+    /// Height of the window in pixels, `0` if unknown.
+    pub height: u16,
+}
+
+impl WindowSize {
+    /// Returns the size of a single cell in pixels `(width, height)`.
+    ///
+    /// Returns `None` if the terminal does not report its size in pixels.
+    pub fn cell_size(&self) -> Option<(u16, u16)> {
+        if self.width == 0 || self.height == 0 || self.columns == 0 || self.rows == 0 {
+            return None;
+        }
+
+        Some((self.width / self.columns, self.height / self.rows))
+    }
+}
+
+/// Returns the terminal window size in cells and pixels.
+///
+/// Use it along with the [`EnablePixelMouseCapture`](../event/struct.EnablePixelMouseCapture.html)
+/// command to convert pixel positions to cells.
+///
+/// # Notes
+///
+/// * The pixel size is not reported by all terminals, it's `0` in such case.
+/// * This function is not supported on Windows.
+pub fn window_size() -> Result<WindowSize> {
+    sys::window_size()
+}
+
+/// Represents the primary device attributes (DA1) of the terminal.
+///
+/// See the [`query_primary_device_attributes`](fn.query_primary_device_attributes.html) function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub struct PrimaryDeviceAttributes {
+    /// The emulated terminal class (`1` VT100, `62` VT220, `63` VT320, `64` VT420, ...).
+    pub class: u16,
+    /// The supported features (`4` Sixel graphics, `22` ANSI color, ...).
+    pub features: Vec<u16>,
+}
+
+/// Represents the secondary device attributes (DA2) of the terminal.
+///
+/// See the [`query_secondary_device_attributes`](fn.query_secondary_device_attributes.html)
+/// function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub struct SecondaryDeviceAttributes {
+    /// The terminal type (`0` VT100, `1` VT220, `41` VT420, ...).
+    pub terminal_type: u16,
+    /// The firmware version, terminals use it for their own version (xterm patch number, ...).
+    pub version: u16,
+    /// The ROM cartridge registration number, usually `0`.
+    pub rom_cartridge: u16,
+}
+
+/// Represents a terminal mode which can be queried with the [`query_mode`](fn.query_mode.html)
+/// function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub enum TerminalMode {
+    /// An ANSI mode (`SM`/`RM`), like `4` (insert mode).
+    Ansi(u16),
+    /// A DEC private mode (`DECSET`/`DECRST`), like `2004` (bracketed paste).
+    Dec(u16),
+}
+
+/// Represents the status of a terminal mode.
+///
+/// See the [`query_mode`](fn.query_mode.html) function.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub enum ModeStatus {
+    /// The terminal doesn't recognize the mode.
+    NotRecognized,
+    /// The mode is set.
+    Set,
+    /// The mode is reset.
+    Reset,
+    /// The mode is set and can't be changed.
+    PermanentlySet,
+    /// The mode is reset and can't be changed.
+    PermanentlyReset,
+}
+
+/// Queries the primary device attributes (DA1) of the terminal.
+///
+/// All terminals should answer this query. Raw mode is enabled for the time of the query
+/// and other events stay available for the [`read`](../event/fn.read.html) function.
+///
+/// # Notes
+///
+/// * An error is returned if the terminal doesn't answer in 2 seconds.
+/// * The response is read by the global reader of the [`read`](../event/fn.read.html)
+///   function, like in all the other queries. An
+///   [`EventReader`](../event/struct.EventReader.html) of the terminal hands the responses
+///   it reads over to it. A late response of an earlier query is dropped before the request
+///   is written.
+/// * This function is not supported on Windows.
+pub fn query_primary_device_attributes() -> Result<PrimaryDeviceAttributes> {
+    sys::query_primary_device_attributes()
+}
+
+/// Queries the secondary device attributes (DA2) of the terminal.
+///
+/// Returns `None` if the terminal doesn't support this query, see the
+/// [`query_primary_device_attributes`](fn.query_primary_device_attributes.html) function
+/// for more details.
+pub fn query_secondary_device_attributes() -> Result<Option<SecondaryDeviceAttributes>> {
+    sys::query_secondary_device_attributes()
+}
+
+/// Queries the terminal name and version (XTVERSION), like `xterm(367)` or `WezTerm 20220207`.
+///
+/// Returns `None` if the terminal doesn't support this query, see the
+/// [`query_primary_device_attributes`](fn.query_primary_device_attributes.html) function
+/// for more details.
+pub fn query_version() -> Result<Option<String>> {
+    sys::query_version()
+}
+
+/// Queries the status of a terminal mode (DECRQM).
+///
+/// Returns `None` if the terminal doesn't support this query, see the
+/// [`query_primary_device_attributes`](fn.query_primary_device_attributes.html) function
+/// for more details.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{
+///     terminal::{query_mode, ModeStatus, TerminalMode},
+///     Result,
+/// };
+///
+/// fn supports_synchronized_output() -> Result<bool> {
+///     Ok(match query_mode(TerminalMode::Dec(2026))? {
+///         Some(ModeStatus::NotRecognized) | Some(ModeStatus::PermanentlyReset) | None => false,
+///         Some(_) => true,
+///     })
+/// }
+/// ```
+pub fn query_mode(mode: TerminalMode) -> Result<Option<ModeStatus>> {
+    sys::query_mode(mode)
+}
+
+/// Queries the default foreground (text) color of the terminal (OSC 10).
+///
+/// The color is returned as [`Color::Rgb`](../style/enum.Color.html#variant.Rgb). Returns
+/// `None` if the terminal doesn't support this query, see the
+/// [`query_primary_device_attributes`](fn.query_primary_device_attributes.html) function
+/// for more details.
+pub fn query_foreground_color() -> Result<Option<Color>> {
+    sys::query_color(10)
+}
+
+/// Queries the default background color of the terminal (OSC 11).
+///
+/// The color is returned as [`Color::Rgb`](../style/enum.Color.html#variant.Rgb). Returns
+/// `None` if the terminal doesn't support this query, see the
+/// [`query_primary_device_attributes`](fn.query_primary_device_attributes.html) function
+/// for more details.
+pub fn query_background_color() -> Result<Option<Color>> {
+    sys::query_color(11)
+}
+
+/// Queries the cursor color of the terminal (OSC 12).
+///
+/// The color is returned as [`Color::Rgb`](../style/enum.Color.html#variant.Rgb). Returns
+/// `None` if the terminal doesn't support this query, see the
+/// [`query_primary_device_attributes`](fn.query_primary_device_attributes.html) function
+/// for more details.
+pub fn query_cursor_color() -> Result<Option<Color>> {
+    sys::query_color(12)
+}
+
+/// Returns whether the terminal background is dark.
+///
+/// The background color is queried with the [`query_background_color`](fn.query_background_color.html)
+/// function, `None` is returned if it's not supported by the terminal.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{terminal::is_dark_background, Result};
+///
+/// fn theme() -> Result<&'static str> {
+///     Ok(match is_dark_background()? {
+///         Some(false) => "light",
+///         _ => "dark",
+///     })
+/// }
+/// ```
+pub fn is_dark_background() -> Result<Option<bool>> {
+    Ok(query_background_color()?.map(is_dark_color))
+}
+
+/// Returns whether the relative luminance of the color is below the middle gray.
+fn is_dark_color(color: Color) -> bool {
+    match color {
+        Color::Rgb { r, g, b } => {
+            let luminance = 0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b);
+            luminance < 128.0
+        }
+        _ => false,
+    }
+}
+
 /// Disables line wrapping.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DisableLineWrap;
@@ -381,7 +751,27 @@ mod tests {
 
     use crate::execute;
 
-    use super::{size, SetSize};
+    #[cfg(unix)]
+    use super::SuspendModes;
+    use super::{is_dark_color, size, Color, SetSize, WindowSize};
+
+    #[cfg(unix)]
+    #[test]
+    fn test_suspend_modes() {
+        let modes = SuspendModes {
+            alternate_screen: true,
+            bracketed_paste: true,
+            ..SuspendModes::default()
+        };
+
+        let mut buffer = Vec::new();
+        modes.disable(&mut buffer).unwrap();
+        assert_eq!(buffer, b"\x1B[?2004l\x1B[?1049l");
+
+        buffer.clear();
+        modes.enable(&mut buffer).unwrap();
+        assert_eq!(buffer, b"\x1B[?1049h\x1B[?2004h");
+    }
 
     // Test is disabled, because it's failing on Travis CI
     #[test]
@@ -407,6 +797,24 @@ mod tests {
         assert_eq!((width, height), size().unwrap());
     }
 
+    #[test]
+    fn test_window_size_cell_size() {
+        let window_size = WindowSize {
+            rows: 24,
+            columns: 80,
+            width: 640,
+            height: 384,
+        };
+        assert_eq!(window_size.cell_size(), Some((8, 16)));
+
+        let window_size = WindowSize {
+            width: 0,
+            height: 0,
+            ..window_size
+        };
+        assert_eq!(window_size.cell_size(), None);
+    }
+
     fn try_enable_ansi() -> bool {
         #[cfg(windows)]
         {
@@ -423,4 +831,24 @@ mod tests {
 
         true
     }
+
+    #[test]
+    fn test_is_dark_color() {
+        assert!(is_dark_color(Color::Rgb { r: 0, g: 0, b: 0 }));
+        assert!(is_dark_color(Color::Rgb {
+            r: 40,
+            g: 42,
+            b: 54
+        }));
+        assert!(!is_dark_color(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255
+        }));
+        assert!(!is_dark_color(Color::Rgb {
+            r: 253,
+            g: 246,
+            b: 227
+        }));
+    }
 }
@@ -1,11 +1,16 @@
 //! This module provides platform related functions.
 
 #[cfg(unix)]
-pub(crate) use self::unix::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, size};
+pub(crate) use self::unix::{
+    disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, query_color, query_mode,
+    query_primary_device_attributes, query_secondary_device_attributes, query_version, size,
+    suspend, suspend_count, window_size, SuspendHandler,
+};
 #[cfg(windows)]
 pub(crate) use self::windows::{
-    clear, disable_raw_mode, enable_raw_mode, scroll_down, scroll_up, set_size, set_window_title,
-    size,
+    clear, disable_raw_mode, enable_raw_mode, query_color, query_mode,
+    query_primary_device_attributes, query_secondary_device_attributes, query_version, scroll_down,
+    scroll_up, set_size, set_window_title, size, suspend, window_size,
 };
 
 #[cfg(windows)]
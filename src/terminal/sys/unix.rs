@@ -1,7 +1,15 @@
 //! UNIX related logic for terminal manipulation.
-use std::{io, mem, process, sync::Mutex};
+use std::{
+    io::{self, Write},
+    mem, process, ptr,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Mutex,
+    },
+    thread,
+};
 
-use crate::event::sys::unix::file_descriptor::{tty_fd, FileDesc};
+use crate::event::sys::unix::file_descriptor::{pipe, tty_fd, FileDesc};
 use lazy_static::lazy_static;
 use libc::{
     cfmakeraw, ioctl, tcgetattr, tcsetattr, termios as Termios, winsize, STDOUT_FILENO, TCSANOW,
@@ -9,6 +17,19 @@ use libc::{
 };
 
 use crate::error::{ErrorKind, Result};
+use crate::event::{
+    filter::{
+        ModeReportFilter, PrimaryDeviceAttributesFilter, SecondaryDeviceAttributesFilter,
+        TerminalColorFilter, TerminalVersionFilter,
+    },
+    query::{query, query_or_unsupported},
+    InternalEvent,
+};
+use crate::style::Color;
+use crate::terminal::{
+    ModeStatus, PrimaryDeviceAttributes, SecondaryDeviceAttributes, SuspendModes, TerminalMode,
+    WindowSize,
+};
 use std::fs::File;
 use std::os::unix::io::{IntoRawFd, RawFd};
 
@@ -16,6 +37,72 @@ lazy_static! {
     // Some(Termios) -> we're in the raw mode and this is the previous mode
     // None -> we're not in the raw mode
     static ref TERMINAL_MODE_PRIOR_RAW_MODE: Mutex<Option<Termios>> = Mutex::new(None);
+    // The state of the `SIGTSTP` handling, it's locked for the whole suspension
+    static ref SUSPEND_HANDLING: Mutex<SuspendHandling> = Mutex::new(SuspendHandling::default());
+}
+
+// The write end of the pipe waking up the thread which suspends the process on `SIGTSTP`,
+// -1 until the thread is started
+static SUSPEND_PIPE: AtomicI32 = AtomicI32::new(-1);
+
+#[derive(Default)]
+struct SuspendHandling {
+    // The number of living `SuspendHandler`s
+    handlers: usize,
+    // The modes of the last created or updated `SuspendHandler`
+    modes: SuspendModes,
+    // The `SIGTSTP` action replaced by `on_sigtstp`, restored by the last `SuspendHandler`
+    previous_action: Option<libc::sigaction>,
+    // How many times the process was suspended
+    count: usize,
+}
+
+/// Handles the `SIGTSTP` signal as long as it's alive.
+///
+/// The terminal is restored (raw mode and the given modes) and the process is stopped right
+/// away once the signal is received, the terminal is reapplied when the process continues.
+pub(crate) struct SuspendHandler(());
+
+impl SuspendHandler {
+    pub(crate) fn new(modes: SuspendModes) -> Result<SuspendHandler> {
+        let mut handling = SUSPEND_HANDLING.lock().unwrap();
+
+        if handling.handlers == 0 {
+            start_suspend_thread()?;
+            let handler = on_sigtstp as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            handling.previous_action = Some(set_sigtstp_action(handler)?);
+        }
+
+        handling.handlers += 1;
+        handling.modes = modes;
+        Ok(SuspendHandler(()))
+    }
+
+    /// Replaces the modes restored when the process is suspended.
+    pub(crate) fn set_modes(&self, modes: SuspendModes) {
+        SUSPEND_HANDLING.lock().unwrap().modes = modes;
+    }
+}
+
+impl Drop for SuspendHandler {
+    fn drop(&mut self) {
+        let mut handling = SUSPEND_HANDLING.lock().unwrap();
+        handling.handlers -= 1;
+
+        if handling.handlers == 0 {
+            if let Some(action) = handling.previous_action.take() {
+                let _ = restore_sigtstp_action(&action);
+            }
+            handling.modes = SuspendModes::default();
+        }
+    }
+}
+
+/// Returns how many times the process was suspended.
+///
+/// It waits for a suspension in progress to finish, the terminal is reapplied afterwards.
+pub(crate) fn suspend_count() -> usize {
+    SUSPEND_HANDLING.lock().unwrap().count
 }
 
 pub(crate) fn is_raw_mode_enabled() -> bool {
@@ -23,7 +110,7 @@ pub(crate) fn is_raw_mode_enabled() -> bool {
 }
 
 #[allow(clippy::useless_conversion)]
-pub(crate) fn size() -> Result<(u16, u16)> {
+pub(crate) fn window_size() -> Result<WindowSize> {
     // http://rosettacode.org/wiki/Terminal_control/Dimensions#Library:_BSD_libc
     let mut size = winsize {
         ws_row: 0,
@@ -40,13 +127,70 @@ pub(crate) fn size() -> Result<(u16, u16)> {
         STDOUT_FILENO
     };
 
-    if let Ok(true) = wrap_with_result(unsafe { ioctl(fd, TIOCGWINSZ.into(), &mut size) }) {
-        Ok((size.ws_col, size.ws_row))
+    wrap_with_result(unsafe { ioctl(fd, TIOCGWINSZ.into(), &mut size) })?;
+
+    Ok(WindowSize {
+        rows: size.ws_row,
+        columns: size.ws_col,
+        width: size.ws_xpixel,
+        height: size.ws_ypixel,
+    })
+}
+
+pub(crate) fn size() -> Result<(u16, u16)> {
+    if let Ok(window_size) = window_size() {
+        Ok((window_size.columns, window_size.rows))
     } else {
         tput_size().ok_or_else(|| std::io::Error::last_os_error().into())
     }
 }
 
+pub(crate) fn query_primary_device_attributes() -> Result<PrimaryDeviceAttributes> {
+    match query(b"\x1B[c", &PrimaryDeviceAttributesFilter)? {
+        InternalEvent::PrimaryDeviceAttributes(attributes) => Ok(attributes),
+        _ => unreachable!(),
+    }
+}
+
+pub(crate) fn query_secondary_device_attributes() -> Result<Option<SecondaryDeviceAttributes>> {
+    match query_or_unsupported(b"\x1B[>c", &SecondaryDeviceAttributesFilter)? {
+        Some(InternalEvent::SecondaryDeviceAttributes(attributes)) => Ok(Some(attributes)),
+        None => Ok(None),
+        _ => unreachable!(),
+    }
+}
+
+pub(crate) fn query_version() -> Result<Option<String>> {
+    match query_or_unsupported(b"\x1B[>0q", &TerminalVersionFilter)? {
+        Some(InternalEvent::TerminalVersion(version)) => Ok(Some(version)),
+        None => Ok(None),
+        _ => unreachable!(),
+    }
+}
+
+pub(crate) fn query_mode(mode: TerminalMode) -> Result<Option<ModeStatus>> {
+    let request = match mode {
+        TerminalMode::Ansi(mode) => format!("\x1B[{}$p", mode),
+        TerminalMode::Dec(mode) => format!("\x1B[?{}$p", mode),
+    };
+
+    match query_or_unsupported(request.as_bytes(), &ModeReportFilter(mode))? {
+        Some(InternalEvent::ModeReport(_, status)) => Ok(Some(status)),
+        None => Ok(None),
+        _ => unreachable!(),
+    }
+}
+
+pub(crate) fn query_color(code: u16) -> Result<Option<Color>> {
+    let request = format!("\x1B]{};?\x1B\\", code);
+
+    match query_or_unsupported(request.as_bytes(), &TerminalColorFilter(code))? {
+        Some(InternalEvent::TerminalColor(_, color)) => Ok(Some(color)),
+        None => Ok(None),
+        _ => unreachable!(),
+    }
+}
+
 pub(crate) fn enable_raw_mode() -> Result<()> {
     let mut original_mode = TERMINAL_MODE_PRIOR_RAW_MODE.lock().unwrap();
 
@@ -81,6 +225,146 @@ pub(crate) fn disable_raw_mode() -> Result<()> {
     Ok(())
 }
 
+/// Restores the terminal, stops the process group and reapplies the terminal once
+/// the process continues.
+pub(crate) fn suspend() -> Result<()> {
+    suspend_process(&mut SUSPEND_HANDLING.lock().unwrap(), true)
+}
+
+/// Restores the terminal, stops the process (or the whole process group) with the default
+/// `SIGTSTP` action and reapplies the terminal once the process continues.
+fn suspend_process(handling: &mut SuspendHandling, process_group: bool) -> Result<()> {
+    // Keep it locked, the raw mode can't be changed by another thread in the meantime
+    let prior_raw_mode = TERMINAL_MODE_PRIOR_RAW_MODE.lock().unwrap();
+    let mut stdout = io::stdout();
+
+    handling.modes.disable(&mut stdout)?;
+    stdout.flush()?;
+
+    let raw_mode = match *prior_raw_mode {
+        Some(prior_raw_mode) => {
+            let tty = tty_fd()?;
+            let raw_mode = get_terminal_attr(tty.raw_fd())?;
+            set_terminal_attr(tty.raw_fd(), &prior_raw_mode)?;
+            Some((tty, raw_mode))
+        }
+        None => None,
+    };
+
+    handling.count += 1;
+
+    // The default action stops the process before `kill` & `raise` return
+    let action = set_sigtstp_action(libc::SIG_DFL)?;
+    let result = wrap_with_result(unsafe {
+        if process_group {
+            libc::kill(0, libc::SIGTSTP)
+        } else {
+            libc::raise(libc::SIGTSTP)
+        }
+    });
+    restore_sigtstp_action(&action)?;
+    result?;
+
+    if let Some((tty, raw_mode)) = raw_mode {
+        set_terminal_attr(tty.raw_fd(), &raw_mode)?;
+    }
+
+    handling.modes.enable(&mut stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+extern "C" fn on_sigtstp(_: libc::c_int) {
+    // Only async-signal-safe functions can be called here, the process is suspended
+    // by the thread waiting for this byte
+    let fd = SUSPEND_PIPE.load(Ordering::SeqCst);
+    let _ = unsafe { libc::write(fd, [0u8].as_ptr() as *const libc::c_void, 1) };
+}
+
+/// Starts the thread suspending the process once `on_sigtstp` writes to `SUSPEND_PIPE`.
+fn start_suspend_thread() -> Result<()> {
+    if SUSPEND_PIPE.load(Ordering::SeqCst) != -1 {
+        return Ok(());
+    }
+
+    let (read_fd, write_fd) = pipe()?;
+    // The signal handler must not block when the pipe is full
+    let write_fd = FileDesc::new(write_fd, true);
+    unsafe {
+        let flags = libc::fcntl(write_fd.raw_fd(), libc::F_GETFL);
+        if flags < 0 || libc::fcntl(write_fd.raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK) < 0
+        {
+            return Err(ErrorKind::IoError(io::Error::last_os_error()));
+        }
+    }
+
+    thread::Builder::new()
+        .name("crossterm-suspend".to_string())
+        .spawn(move || {
+            // `raise` would wait for the signal to be unblocked
+            unsafe {
+                let mut signals = mem::zeroed();
+                libc::sigemptyset(&mut signals);
+                libc::sigaddset(&mut signals, libc::SIGTSTP);
+                libc::pthread_sigmask(libc::SIG_UNBLOCK, &signals, ptr::null_mut());
+            }
+
+            let mut buffer = [0u8; 32];
+            let size = buffer.len();
+            loop {
+                let mut poll_fd = libc::pollfd {
+                    fd: read_fd.raw_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                };
+                if unsafe { libc::poll(&mut poll_fd, 1, -1) } < 0 {
+                    continue;
+                }
+
+                // More signals can be pending, the process is suspended just once
+                while let Ok(count) = read_fd.read(&mut buffer, size) {
+                    if count == 0 {
+                        break;
+                    }
+                }
+
+                let mut handling = SUSPEND_HANDLING.lock().unwrap();
+                // The handling could be disabled in the meantime
+                if handling.handlers > 0 {
+                    let _ = suspend_process(&mut handling, false);
+                }
+            }
+        })?;
+
+    // The write end is never closed, the thread lives as long as the process
+    SUSPEND_PIPE.store(write_fd.raw_fd(), Ordering::SeqCst);
+    mem::forget(write_fd);
+    Ok(())
+}
+
+/// Sets the action of the `SIGTSTP` signal, returns the previous one.
+fn set_sigtstp_action(handler: libc::sighandler_t) -> Result<libc::sigaction> {
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = handler;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        let mut previous_action = mem::zeroed();
+        wrap_with_result(libc::sigaction(
+            libc::SIGTSTP,
+            &action,
+            &mut previous_action,
+        ))?;
+        Ok(previous_action)
+    }
+}
+
+fn restore_sigtstp_action(action: &libc::sigaction) -> Result<()> {
+    wrap_with_result(unsafe { libc::sigaction(libc::SIGTSTP, action, ptr::null_mut()) })?;
+    Ok(())
+}
+
 /// execute tput with the given argument and parse
 /// the output as a u16.
 ///
@@ -5,7 +5,17 @@ use winapi::{
     um::wincon::{SetConsoleTitleW, ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT},
 };
 
-use crate::{cursor, terminal::ClearType, ErrorKind, Result};
+use std::io;
+
+use crate::{
+    cursor,
+    style::Color,
+    terminal::{
+        ClearType, ModeStatus, PrimaryDeviceAttributes, SecondaryDeviceAttributes, TerminalMode,
+        WindowSize,
+    },
+    ErrorKind, Result,
+};
 
 const RAW_MODE_MASK: DWORD = ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_PROCESSED_INPUT;
 
@@ -42,6 +52,38 @@ pub(crate) fn size() -> Result<(u16, u16)> {
     ))
 }
 
+pub(crate) fn window_size() -> Result<WindowSize> {
+    Err(io::Error::other("Window pixel size is not supported by the Windows API.").into())
+}
+
+pub(crate) fn query_primary_device_attributes() -> Result<PrimaryDeviceAttributes> {
+    Err(queries_not_supported())
+}
+
+pub(crate) fn query_secondary_device_attributes() -> Result<Option<SecondaryDeviceAttributes>> {
+    Err(queries_not_supported())
+}
+
+pub(crate) fn query_version() -> Result<Option<String>> {
+    Err(queries_not_supported())
+}
+
+pub(crate) fn query_mode(_mode: TerminalMode) -> Result<Option<ModeStatus>> {
+    Err(queries_not_supported())
+}
+
+pub(crate) fn query_color(_code: u16) -> Result<Option<Color>> {
+    Err(queries_not_supported())
+}
+
+pub(crate) fn suspend() -> Result<()> {
+    Err(io::Error::other("Suspending the process is not supported on Windows.").into())
+}
+
+fn queries_not_supported() -> ErrorKind {
+    io::Error::other("Terminal queries are not supported by the Windows API.").into()
+}
+
 pub(crate) fn clear(clear_type: ClearType) -> Result<()> {
     let screen_buffer = ScreenBuffer::current()?;
     let csbi = screen_buffer.info()?;